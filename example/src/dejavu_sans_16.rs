@@ -0,0 +1,204 @@
+// Generated by font2rs from DejaVuSans.ttf.
+
+use mono_display::gfx::{StaticFont, StaticGlyph};
+
+pub static DEJAVU_SANS_16: StaticFont = StaticFont {
+    ascent: 15,
+    descent: 4,
+    line_height: 19,
+    pixel_size: 16,
+    default_char: None,
+    glyphs: &[
+        StaticGlyph { codepoint: 0x20, left: 0, top: 0, width: 0, height: 0, advance: 5.0859375, offset: 0 },
+        StaticGlyph { codepoint: 0x21, left: 2, top: -12, width: 2, height: 12, advance: 6.4140625, offset: 0 },
+        StaticGlyph { codepoint: 0x22, left: 1, top: -12, width: 5, height: 5, advance: 7.359375, offset: 4 },
+        StaticGlyph { codepoint: 0x23, left: 1, top: -12, width: 12, height: 12, advance: 13.40625, offset: 9 },
+        StaticGlyph { codepoint: 0x24, left: 1, top: -13, width: 8, height: 16, advance: 10.1796875, offset: 33 },
+        StaticGlyph { codepoint: 0x25, left: 0, top: -12, width: 15, height: 13, advance: 15.203125, offset: 49 },
+        StaticGlyph { codepoint: 0x26, left: 1, top: -12, width: 11, height: 13, advance: 12.4765625, offset: 79 },
+        StaticGlyph { codepoint: 0x27, left: 1, top: -12, width: 2, height: 5, advance: 4.3984375, offset: 101 },
+        StaticGlyph { codepoint: 0x28, left: 1, top: -13, width: 4, height: 16, advance: 6.2421875, offset: 103 },
+        StaticGlyph { codepoint: 0x29, left: 1, top: -13, width: 4, height: 16, advance: 6.2421875, offset: 111 },
+        StaticGlyph { codepoint: 0x2a, left: 0, top: -12, width: 8, height: 8, advance: 8.0, offset: 119 },
+        StaticGlyph { codepoint: 0x2b, left: 1, top: -11, width: 11, height: 11, advance: 13.40625, offset: 127 },
+        StaticGlyph { codepoint: 0x2c, left: 1, top: -2, width: 3, height: 4, advance: 5.0859375, offset: 149 },
+        StaticGlyph { codepoint: 0x2d, left: 0, top: -6, width: 5, height: 3, advance: 5.7734375, offset: 152 },
+        StaticGlyph { codepoint: 0x2e, left: 1, top: -2, width: 3, height: 2, advance: 5.0859375, offset: 157 },
+        StaticGlyph { codepoint: 0x2f, left: 0, top: -12, width: 6, height: 14, advance: 5.390625, offset: 160 },
+        StaticGlyph { codepoint: 0x30, left: 1, top: -12, width: 9, height: 13, advance: 10.1796875, offset: 172 },
+        StaticGlyph { codepoint: 0x31, left: 1, top: -12, width: 8, height: 12, advance: 10.1796875, offset: 190 },
+        StaticGlyph { codepoint: 0x32, left: 1, top: -12, width: 8, height: 12, advance: 10.1796875, offset: 206 },
+        StaticGlyph { codepoint: 0x33, left: 1, top: -12, width: 8, height: 13, advance: 10.1796875, offset: 222 },
+        StaticGlyph { codepoint: 0x34, left: 0, top: -12, width: 10, height: 12, advance: 10.1796875, offset: 238 },
+        StaticGlyph { codepoint: 0x35, left: 1, top: -12, width: 8, height: 13, advance: 10.1796875, offset: 258 },
+        StaticGlyph { codepoint: 0x36, left: 1, top: -12, width: 9, height: 13, advance: 10.1796875, offset: 274 },
+        StaticGlyph { codepoint: 0x37, left: 1, top: -12, width: 8, height: 12, advance: 10.1796875, offset: 292 },
+        StaticGlyph { codepoint: 0x38, left: 1, top: -12, width: 9, height: 13, advance: 10.1796875, offset: 308 },
+        StaticGlyph { codepoint: 0x39, left: 1, top: -12, width: 9, height: 13, advance: 10.1796875, offset: 326 },
+        StaticGlyph { codepoint: 0x3a, left: 1, top: -9, width: 3, height: 9, advance: 5.390625, offset: 344 },
+        StaticGlyph { codepoint: 0x3b, left: 1, top: -9, width: 3, height: 11, advance: 5.390625, offset: 350 },
+        StaticGlyph { codepoint: 0x3c, left: 1, top: -10, width: 11, height: 10, advance: 13.40625, offset: 356 },
+        StaticGlyph { codepoint: 0x3d, left: 1, top: -8, width: 11, height: 6, advance: 13.40625, offset: 378 },
+        StaticGlyph { codepoint: 0x3e, left: 1, top: -10, width: 11, height: 10, advance: 13.40625, offset: 389 },
+        StaticGlyph { codepoint: 0x3f, left: 1, top: -12, width: 7, height: 12, advance: 8.4921875, offset: 411 },
+        StaticGlyph { codepoint: 0x40, left: 1, top: -12, width: 14, height: 15, advance: 16.0, offset: 425 },
+        StaticGlyph { codepoint: 0x41, left: 0, top: -12, width: 11, height: 12, advance: 10.9453125, offset: 453 },
+        StaticGlyph { codepoint: 0x42, left: 1, top: -12, width: 9, height: 12, advance: 10.9765625, offset: 475 },
+        StaticGlyph { codepoint: 0x43, left: 0, top: -12, width: 11, height: 13, advance: 11.171875, offset: 493 },
+        StaticGlyph { codepoint: 0x44, left: 1, top: -12, width: 11, height: 12, advance: 12.3203125, offset: 515 },
+        StaticGlyph { codepoint: 0x45, left: 1, top: -12, width: 9, height: 12, advance: 10.109375, offset: 537 },
+        StaticGlyph { codepoint: 0x46, left: 1, top: -12, width: 8, height: 12, advance: 9.203125, offset: 555 },
+        StaticGlyph { codepoint: 0x47, left: 0, top: -12, width: 12, height: 13, advance: 12.3984375, offset: 571 },
+        StaticGlyph { codepoint: 0x48, left: 1, top: -12, width: 10, height: 12, advance: 12.03125, offset: 595 },
+        StaticGlyph { codepoint: 0x49, left: 1, top: -12, width: 3, height: 12, advance: 4.71875, offset: 615 },
+        StaticGlyph { codepoint: 0x4a, left: -1, top: -12, width: 5, height: 16, advance: 4.71875, offset: 621 },
+        StaticGlyph { codepoint: 0x4b, left: 1, top: -12, width: 10, height: 12, advance: 10.4921875, offset: 631 },
+        StaticGlyph { codepoint: 0x4c, left: 1, top: -12, width: 8, height: 12, advance: 8.9140625, offset: 651 },
+        StaticGlyph { codepoint: 0x4d, left: 1, top: -12, width: 12, height: 12, advance: 13.8046875, offset: 667 },
+        StaticGlyph { codepoint: 0x4e, left: 1, top: -12, width: 10, height: 12, advance: 11.96875, offset: 691 },
+        StaticGlyph { codepoint: 0x4f, left: 0, top: -12, width: 12, height: 13, advance: 12.59375, offset: 711 },
+        StaticGlyph { codepoint: 0x50, left: 1, top: -12, width: 9, height: 12, advance: 9.6484375, offset: 735 },
+        StaticGlyph { codepoint: 0x51, left: 0, top: -12, width: 12, height: 15, advance: 12.59375, offset: 753 },
+        StaticGlyph { codepoint: 0x52, left: 1, top: -12, width: 10, height: 12, advance: 11.1171875, offset: 777 },
+        StaticGlyph { codepoint: 0x53, left: 1, top: -12, width: 9, height: 13, advance: 10.15625, offset: 797 },
+        StaticGlyph { codepoint: 0x54, left: -1, top: -12, width: 11, height: 12, advance: 9.7734375, offset: 815 },
+        StaticGlyph { codepoint: 0x55, left: 1, top: -12, width: 10, height: 13, advance: 11.7109375, offset: 837 },
+        StaticGlyph { codepoint: 0x56, left: 0, top: -12, width: 11, height: 12, advance: 10.9453125, offset: 857 },
+        StaticGlyph { codepoint: 0x57, left: 0, top: -12, width: 16, height: 12, advance: 15.8203125, offset: 879 },
+        StaticGlyph { codepoint: 0x58, left: 0, top: -12, width: 11, height: 12, advance: 10.9609375, offset: 911 },
+        StaticGlyph { codepoint: 0x59, left: -1, top: -12, width: 11, height: 12, advance: 9.7734375, offset: 933 },
+        StaticGlyph { codepoint: 0x5a, left: 0, top: -12, width: 11, height: 12, advance: 10.9609375, offset: 955 },
+        StaticGlyph { codepoint: 0x5b, left: 1, top: -13, width: 4, height: 16, advance: 6.2421875, offset: 977 },
+        StaticGlyph { codepoint: 0x5c, left: 0, top: -12, width: 6, height: 14, advance: 5.390625, offset: 985 },
+        StaticGlyph { codepoint: 0x5d, left: 1, top: -13, width: 4, height: 16, advance: 6.2421875, offset: 997 },
+        StaticGlyph { codepoint: 0x5e, left: 1, top: -12, width: 11, height: 5, advance: 13.40625, offset: 1005 },
+        StaticGlyph { codepoint: 0x5f, left: -1, top: 2, width: 10, height: 2, advance: 8.0, offset: 1016 },
+        StaticGlyph { codepoint: 0x60, left: 1, top: -13, width: 5, height: 4, advance: 8.0, offset: 1026 },
+        StaticGlyph { codepoint: 0x61, left: 0, top: -9, width: 9, height: 10, advance: 9.8046875, offset: 1031 },
+        StaticGlyph { codepoint: 0x62, left: 1, top: -13, width: 9, height: 14, advance: 10.15625, offset: 1049 },
+        StaticGlyph { codepoint: 0x63, left: 0, top: -9, width: 8, height: 10, advance: 8.796875, offset: 1067 },
+        StaticGlyph { codepoint: 0x64, left: 0, top: -13, width: 9, height: 14, advance: 10.15625, offset: 1083 },
+        StaticGlyph { codepoint: 0x65, left: 0, top: -9, width: 9, height: 10, advance: 9.84375, offset: 1101 },
+        StaticGlyph { codepoint: 0x66, left: 0, top: -13, width: 6, height: 13, advance: 5.6328125, offset: 1119 },
+        StaticGlyph { codepoint: 0x67, left: 0, top: -9, width: 9, height: 13, advance: 10.15625, offset: 1131 },
+        StaticGlyph { codepoint: 0x68, left: 1, top: -13, width: 8, height: 13, advance: 10.140625, offset: 1149 },
+        StaticGlyph { codepoint: 0x69, left: 1, top: -13, width: 2, height: 13, advance: 4.4453125, offset: 1165 },
+        StaticGlyph { codepoint: 0x6a, left: -1, top: -13, width: 4, height: 17, advance: 4.4453125, offset: 1169 },
+        StaticGlyph { codepoint: 0x6b, left: 1, top: -13, width: 9, height: 13, advance: 9.265625, offset: 1181 },
+        StaticGlyph { codepoint: 0x6c, left: 1, top: -13, width: 2, height: 13, advance: 4.4453125, offset: 1199 },
+        StaticGlyph { codepoint: 0x6d, left: 1, top: -9, width: 14, height: 9, advance: 15.5859375, offset: 1203 },
+        StaticGlyph { codepoint: 0x6e, left: 1, top: -9, width: 8, height: 9, advance: 10.140625, offset: 1231 },
+        StaticGlyph { codepoint: 0x6f, left: 0, top: -9, width: 9, height: 10, advance: 9.7890625, offset: 1247 },
+        StaticGlyph { codepoint: 0x70, left: 1, top: -9, width: 9, height: 13, advance: 10.15625, offset: 1265 },
+        StaticGlyph { codepoint: 0x71, left: 0, top: -9, width: 9, height: 13, advance: 10.15625, offset: 1283 },
+        StaticGlyph { codepoint: 0x72, left: 1, top: -9, width: 6, height: 9, advance: 6.578125, offset: 1301 },
+        StaticGlyph { codepoint: 0x73, left: 0, top: -9, width: 8, height: 10, advance: 8.3359375, offset: 1313 },
+        StaticGlyph { codepoint: 0x74, left: 0, top: -12, width: 6, height: 12, advance: 6.2734375, offset: 1329 },
+        StaticGlyph { codepoint: 0x75, left: 1, top: -9, width: 8, height: 10, advance: 10.140625, offset: 1341 },
+        StaticGlyph { codepoint: 0x76, left: 0, top: -9, width: 9, height: 9, advance: 9.46875, offset: 1357 },
+        StaticGlyph { codepoint: 0x77, left: 0, top: -9, width: 13, height: 9, advance: 13.0859375, offset: 1375 },
+        StaticGlyph { codepoint: 0x78, left: 0, top: -9, width: 9, height: 9, advance: 9.46875, offset: 1401 },
+        StaticGlyph { codepoint: 0x79, left: 0, top: -9, width: 9, height: 13, advance: 9.46875, offset: 1419 },
+        StaticGlyph { codepoint: 0x7a, left: 0, top: -9, width: 8, height: 9, advance: 8.3984375, offset: 1437 },
+        StaticGlyph { codepoint: 0x7b, left: 2, top: -13, width: 7, height: 16, advance: 10.1796875, offset: 1453 },
+        StaticGlyph { codepoint: 0x7c, left: 2, top: -13, width: 2, height: 17, advance: 5.390625, offset: 1467 },
+        StaticGlyph { codepoint: 0x7d, left: 2, top: -13, width: 7, height: 16, advance: 10.1796875, offset: 1473 },
+        StaticGlyph { codepoint: 0x7e, left: 1, top: -7, width: 11, height: 4, advance: 13.40625, offset: 1487 },
+    ],
+    data: &[
+        0x7e, 0xff, 0x0c, 0x0c, 0x00, 0x1f, 0x00, 0x0e, 0x1f, 0x00, 0x10, 0x10, 0xd0, 0xfc, 0x16, 0x90,
+        0xf8, 0x1e, 0x10, 0x10, 0x00, 0x01, 0x01, 0x0d, 0x07, 0x01, 0x01, 0x0f, 0x01, 0x01, 0x01, 0x00,
+        0x00, 0x20, 0xf8, 0x88, 0x8c, 0x8c, 0x88, 0x08, 0x00, 0x08, 0x18, 0x10, 0x11, 0x19, 0x19, 0x0f,
+        0x06, 0x00, 0x1e, 0x23, 0x21, 0x23, 0x3e, 0x80, 0x60, 0x30, 0x8c, 0xc3, 0x41, 0x40, 0x80, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x0c, 0x06, 0x01, 0x00, 0x00, 0x07, 0x0c, 0x08, 0x08, 0x07, 0x00, 0x80,
+        0xcc, 0x3e, 0x33, 0x61, 0xc1, 0x83, 0x00, 0x00, 0xc0, 0x00, 0x03, 0x07, 0x0c, 0x08, 0x08, 0x08,
+        0x0d, 0x07, 0x07, 0x0d, 0x00, 0x00, 0x1f, 0x80, 0xf8, 0x0e, 0x02, 0x01, 0x1f, 0x70, 0x40, 0x00,
+        0x0e, 0xf8, 0xe0, 0x00, 0x70, 0x1f, 0x07, 0x00, 0x24, 0x14, 0x18, 0x18, 0x14, 0x24, 0x00, 0x00,
+        0x60, 0x60, 0x60, 0x60, 0xfe, 0x60, 0x60, 0x60, 0x60, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x07, 0x01, 0x00, 0x02, 0x02, 0x02, 0x02, 0x00, 0x03, 0x00,
+        0x00, 0x00, 0xe0, 0x3c, 0x07, 0x00, 0x10, 0x1e, 0x03, 0x00, 0x00, 0x00, 0xf8, 0xfe, 0x02, 0x03,
+        0x01, 0x03, 0xbe, 0xf8, 0x00, 0x01, 0x07, 0x0c, 0x08, 0x08, 0x0c, 0x07, 0x03, 0x00, 0x00, 0x02,
+        0x02, 0x03, 0xff, 0x00, 0x00, 0x00, 0x00, 0x08, 0x08, 0x0c, 0x0f, 0x08, 0x08, 0x08, 0x02, 0x03,
+        0x03, 0x81, 0xc3, 0x62, 0x3e, 0x00, 0x0c, 0x0e, 0x0f, 0x09, 0x08, 0x08, 0x08, 0x08, 0x00, 0x03,
+        0x23, 0x21, 0x63, 0x63, 0xde, 0x88, 0x0c, 0x08, 0x08, 0x08, 0x08, 0x0c, 0x07, 0x03, 0x00, 0x80,
+        0x60, 0x30, 0x0c, 0x06, 0xff, 0xfe, 0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x0f, 0x0f,
+        0x01, 0x00, 0x00, 0x3f, 0x33, 0x33, 0x33, 0x63, 0xe3, 0x80, 0x0c, 0x08, 0x08, 0x08, 0x08, 0x0c,
+        0x07, 0x03, 0xf0, 0xfc, 0x26, 0x23, 0x33, 0x33, 0xe3, 0xc0, 0x00, 0x01, 0x07, 0x0c, 0x08, 0x08,
+        0x08, 0x0e, 0x07, 0x00, 0x00, 0x03, 0x03, 0x03, 0xe3, 0x7b, 0x0f, 0x03, 0x00, 0x00, 0x08, 0x0f,
+        0x03, 0x00, 0x00, 0x00, 0x8c, 0xde, 0x63, 0x61, 0x61, 0x63, 0xde, 0x8c, 0x00, 0x03, 0x0f, 0x0c,
+        0x08, 0x08, 0x08, 0x0f, 0x07, 0x00, 0x3c, 0x7e, 0xc3, 0x81, 0x81, 0x42, 0xfe, 0xf8, 0x00, 0x00,
+        0x08, 0x08, 0x08, 0x08, 0x0c, 0x07, 0x01, 0x00, 0x00, 0x86, 0x82, 0x00, 0x01, 0x01, 0x00, 0x86,
+        0x82, 0x04, 0x03, 0x00, 0x00, 0x30, 0x30, 0x78, 0x48, 0x48, 0xcc, 0x84, 0x84, 0x86, 0x02, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x12, 0x12, 0x12, 0x12, 0x12,
+        0x12, 0x12, 0x12, 0x12, 0x12, 0x00, 0x02, 0x86, 0x84, 0x84, 0xcc, 0x48, 0x78, 0x30, 0x30, 0x30,
+        0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xc1, 0xe3, 0x33,
+        0x1e, 0x00, 0x00, 0x00, 0x0d, 0x0d, 0x00, 0x00, 0x00, 0xc0, 0x30, 0x08, 0x04, 0xe6, 0x22, 0x32,
+        0x32, 0x22, 0xe2, 0x04, 0x0c, 0x18, 0xe0, 0x03, 0x0c, 0x18, 0x30, 0x23, 0x26, 0x44, 0x44, 0x66,
+        0x27, 0x24, 0x04, 0x03, 0x01, 0x00, 0x00, 0xc0, 0xf8, 0x1e, 0x07, 0x1e, 0xf8, 0xc0, 0x00, 0x00,
+        0x08, 0x0f, 0x03, 0x01, 0x01, 0x01, 0x01, 0x01, 0x03, 0x0e, 0x08, 0x00, 0xff, 0x63, 0x23, 0x23,
+        0x63, 0x76, 0xde, 0x80, 0x00, 0x0f, 0x08, 0x08, 0x08, 0x08, 0x0c, 0x07, 0x03, 0x00, 0xf8, 0xfc,
+        0x06, 0x02, 0x03, 0x01, 0x03, 0x03, 0x02, 0x00, 0x00, 0x01, 0x07, 0x06, 0x0c, 0x08, 0x08, 0x08,
+        0x08, 0x04, 0x00, 0x00, 0xff, 0x03, 0x03, 0x03, 0x02, 0x02, 0x06, 0x1c, 0xf8, 0x00, 0x00, 0x0f,
+        0x08, 0x08, 0x08, 0x08, 0x0c, 0x04, 0x07, 0x01, 0x00, 0x00, 0xff, 0x63, 0x23, 0x23, 0x23, 0x23,
+        0x23, 0x00, 0x00, 0x0f, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x00, 0x00, 0xff, 0x63, 0x23, 0x23,
+        0x23, 0x23, 0x00, 0x00, 0x0f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf8, 0xfc, 0x06, 0x02,
+        0x03, 0x01, 0x43, 0x43, 0xc2, 0xc6, 0x00, 0x00, 0x01, 0x07, 0x06, 0x0c, 0x08, 0x08, 0x08, 0x08,
+        0x0c, 0x07, 0x00, 0x00, 0xff, 0x60, 0x20, 0x20, 0x20, 0x20, 0x60, 0xff, 0x00, 0x00, 0x0f, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x0f, 0x00, 0x00, 0xff, 0x00, 0x00, 0x0f, 0x00, 0x00, 0x00, 0x00,
+        0xff, 0x00, 0x40, 0x40, 0x70, 0x3f, 0x00, 0x00, 0xff, 0x60, 0xf0, 0x98, 0x0c, 0x06, 0x03, 0x01,
+        0x00, 0x00, 0x0f, 0x00, 0x00, 0x01, 0x03, 0x06, 0x0c, 0x08, 0x00, 0x00, 0xff, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x0f, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x00, 0xff, 0x07, 0x1c, 0xe0,
+        0x80, 0x80, 0xf0, 0x1e, 0x07, 0xff, 0x00, 0x00, 0x0f, 0x00, 0x00, 0x00, 0x03, 0x03, 0x00, 0x00,
+        0x00, 0x0f, 0x00, 0x00, 0xff, 0x07, 0x1c, 0x70, 0xc0, 0x00, 0x00, 0xff, 0x00, 0x00, 0x0f, 0x00,
+        0x00, 0x00, 0x01, 0x07, 0x0e, 0x0f, 0x00, 0x00, 0xf8, 0xfc, 0x06, 0x03, 0x03, 0x01, 0x03, 0x02,
+        0x0e, 0xfc, 0x60, 0x00, 0x01, 0x07, 0x06, 0x0c, 0x08, 0x08, 0x08, 0x0c, 0x06, 0x03, 0x00, 0x00,
+        0xff, 0x43, 0x43, 0x43, 0x42, 0x7e, 0x3c, 0x00, 0x00, 0x0f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0xf8, 0xfc, 0x06, 0x03, 0x03, 0x01, 0x03, 0x02, 0x0e, 0xfc, 0xe0, 0x00, 0x01, 0x07,
+        0x06, 0x0c, 0x08, 0x08, 0x18, 0x3c, 0x26, 0x03, 0x00, 0x00, 0xff, 0x43, 0x43, 0x43, 0xe2, 0xfe,
+        0x3c, 0x00, 0x00, 0x00, 0x0f, 0x00, 0x00, 0x00, 0x00, 0x01, 0x07, 0x0c, 0x00, 0x1c, 0x3e, 0x23,
+        0x61, 0x61, 0x43, 0xc3, 0x82, 0x00, 0x0c, 0x0c, 0x08, 0x08, 0x08, 0x08, 0x0e, 0x07, 0x00, 0x00,
+        0x03, 0x03, 0x03, 0x03, 0xff, 0xff, 0x03, 0x03, 0x03, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0f,
+        0x0f, 0x00, 0x00, 0x00, 0x00, 0xfe, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x00, 0x00,
+        0x07, 0x0c, 0x08, 0x08, 0x08, 0x08, 0x06, 0x07, 0x00, 0x00, 0x0f, 0x7c, 0xe0, 0x00, 0x00, 0x80,
+        0xe0, 0x3c, 0x0f, 0x00, 0x00, 0x00, 0x00, 0x01, 0x0f, 0x0c, 0x0f, 0x01, 0x00, 0x00, 0x00, 0x00,
+        0x0f, 0xfc, 0xc0, 0x00, 0x80, 0xfc, 0x0f, 0x0f, 0xf8, 0x80, 0x00, 0xe0, 0x7e, 0x07, 0x00, 0x00,
+        0x00, 0x00, 0x0f, 0x0e, 0x0f, 0x00, 0x00, 0x00, 0x00, 0x0f, 0x0e, 0x07, 0x00, 0x00, 0x00, 0x00,
+        0x01, 0x07, 0x8c, 0xf8, 0x70, 0xf8, 0x0c, 0x06, 0x01, 0x00, 0x00, 0x0c, 0x0e, 0x03, 0x01, 0x00,
+        0x01, 0x03, 0x0e, 0x08, 0x00, 0x00, 0x01, 0x07, 0x0c, 0x38, 0xe0, 0xf0, 0x18, 0x0e, 0x03, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x0f, 0x0f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x03, 0x83, 0xc3,
+        0x63, 0x33, 0x1f, 0x07, 0x03, 0x00, 0x00, 0x0c, 0x0e, 0x0b, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08,
+        0x00, 0xfe, 0xfe, 0x02, 0x02, 0x7f, 0x7f, 0x40, 0x40, 0x03, 0x1e, 0xf0, 0x80, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x0f, 0x18, 0x00, 0x00, 0x02, 0xfe, 0xfe, 0x00, 0x40, 0x7f, 0x7f, 0x00, 0x10, 0x08,
+        0x0c, 0x06, 0x03, 0x03, 0x06, 0x0c, 0x18, 0x00, 0x00, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+        0x02, 0x00, 0x00, 0x03, 0x06, 0x04, 0x00, 0x00, 0xe0, 0xb3, 0x11, 0x11, 0x19, 0x9b, 0xfe, 0x00,
+        0x00, 0x00, 0x01, 0x01, 0x01, 0x01, 0x00, 0x01, 0x00, 0xfe, 0xfe, 0x20, 0x10, 0x10, 0x30, 0x60,
+        0xc0, 0x00, 0x1f, 0x1f, 0x08, 0x10, 0x10, 0x10, 0x1c, 0x0f, 0x00, 0x00, 0x7c, 0xee, 0x83, 0x01,
+        0x01, 0x01, 0x02, 0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x00, 0xc0, 0x60, 0x30, 0x10,
+        0x10, 0x20, 0xfe, 0xfe, 0x00, 0x07, 0x0c, 0x10, 0x10, 0x10, 0x08, 0x1f, 0x1f, 0x00, 0x7c, 0xde,
+        0x93, 0x11, 0x11, 0x13, 0x1e, 0x1c, 0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x00, 0x00,
+        0x30, 0xfc, 0x16, 0x12, 0x02, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00, 0x00, 0x7c, 0xc6, 0x83, 0x01,
+        0x01, 0x82, 0xff, 0xff, 0x00, 0x00, 0x08, 0x09, 0x09, 0x09, 0x0c, 0x07, 0x01, 0xfe, 0xfe, 0x20,
+        0x10, 0x10, 0x30, 0xe0, 0xc0, 0x1f, 0x1f, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x1f, 0x00, 0xf6, 0x00,
+        0x1f, 0x00, 0x00, 0x00, 0xf6, 0x00, 0x80, 0xe0, 0x7f, 0x00, 0x00, 0x00, 0x00, 0xfe, 0xfe, 0x00,
+        0x80, 0x40, 0x20, 0x30, 0x00, 0x00, 0x1f, 0x1f, 0x01, 0x03, 0x06, 0x0c, 0x18, 0x10, 0x00, 0x00,
+        0xfe, 0x00, 0x1f, 0xfe, 0xff, 0x02, 0x01, 0x01, 0x03, 0xfe, 0xfc, 0x02, 0x01, 0x01, 0x03, 0xfe,
+        0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0xfe,
+        0xff, 0x02, 0x01, 0x01, 0x03, 0xfe, 0xfc, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
+        0x7c, 0xce, 0x83, 0x01, 0x01, 0x83, 0xfe, 0x7c, 0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x01, 0x00,
+        0x00, 0xfe, 0xff, 0x82, 0x01, 0x01, 0x03, 0xc6, 0xfc, 0x00, 0x0f, 0x0f, 0x00, 0x01, 0x01, 0x01,
+        0x01, 0x00, 0x00, 0x00, 0x7c, 0xc6, 0x03, 0x01, 0x01, 0x82, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+        0x01, 0x01, 0x00, 0x0f, 0x0f, 0xfe, 0xff, 0x02, 0x03, 0x01, 0x01, 0x01, 0x01, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x8e, 0x1b, 0x11, 0x11, 0x31, 0xe3, 0x40, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x00,
+        0x00, 0x00, 0xfe, 0xfe, 0x08, 0x08, 0x08, 0x00, 0x01, 0x0f, 0x08, 0x08, 0x08, 0x7e, 0xff, 0x80,
+        0x00, 0x00, 0x80, 0xff, 0xff, 0x00, 0x01, 0x01, 0x01, 0x01, 0x00, 0x01, 0x01, 0x00, 0x07, 0x3c,
+        0xf0, 0x80, 0xc0, 0x78, 0x0e, 0x03, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00,
+        0x07, 0x7c, 0xc0, 0xe0, 0x3e, 0x07, 0x1e, 0xf0, 0xc0, 0xfc, 0x0f, 0x00, 0x00, 0x00, 0x00, 0x01,
+        0x01, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0xc7, 0x6c, 0x38, 0x7c, 0xc6,
+        0x83, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x07, 0x1c, 0xf0, 0xc0,
+        0xe0, 0x38, 0x0f, 0x01, 0x00, 0x08, 0x08, 0x0c, 0x07, 0x01, 0x00, 0x00, 0x00, 0x00, 0x81, 0xc1,
+        0x31, 0x19, 0x0d, 0x07, 0x03, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x00, 0x80, 0xf8,
+        0x7e, 0x02, 0x02, 0x00, 0x01, 0x01, 0x1f, 0x7e, 0x40, 0xc0, 0x00, 0xfe, 0x00, 0xff, 0x00, 0x01,
+        0x00, 0x02, 0x02, 0x7e, 0xfc, 0x80, 0x00, 0x00, 0xc0, 0xc0, 0x7e, 0x3f, 0x01, 0x01, 0x00, 0x00,
+        0x06, 0x02, 0x02, 0x02, 0x06, 0x04, 0x04, 0x04, 0x06, 0x02,
+    ],
+};
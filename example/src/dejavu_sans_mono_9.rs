@@ -0,0 +1,142 @@
+// Generated by font2rs from DejaVuSansMono.ttf.
+
+use mono_display::gfx::{StaticFont, StaticGlyph};
+
+pub static DEJAVU_SANS_MONO_9: StaticFont = StaticFont {
+    ascent: 8,
+    descent: 2,
+    line_height: 10,
+    pixel_size: 9,
+    default_char: None,
+    glyphs: &[
+        StaticGlyph { codepoint: 0x20, left: 0, top: 0, width: 0, height: 0, advance: 5.418457, offset: 0 },
+        StaticGlyph { codepoint: 0x21, left: 2, top: -7, width: 2, height: 7, advance: 5.418457, offset: 0 },
+        StaticGlyph { codepoint: 0x22, left: 1, top: -7, width: 3, height: 3, advance: 5.418457, offset: 2 },
+        StaticGlyph { codepoint: 0x23, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 5 },
+        StaticGlyph { codepoint: 0x24, left: 0, top: -7, width: 5, height: 9, advance: 5.418457, offset: 11 },
+        StaticGlyph { codepoint: 0x25, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 21 },
+        StaticGlyph { codepoint: 0x26, left: 0, top: -7, width: 6, height: 8, advance: 5.418457, offset: 27 },
+        StaticGlyph { codepoint: 0x27, left: 2, top: -7, width: 2, height: 3, advance: 5.418457, offset: 33 },
+        StaticGlyph { codepoint: 0x28, left: 1, top: -7, width: 3, height: 9, advance: 5.418457, offset: 35 },
+        StaticGlyph { codepoint: 0x29, left: 1, top: -7, width: 3, height: 9, advance: 5.418457, offset: 41 },
+        StaticGlyph { codepoint: 0x2a, left: 0, top: -7, width: 5, height: 5, advance: 5.418457, offset: 47 },
+        StaticGlyph { codepoint: 0x2b, left: 0, top: -6, width: 6, height: 6, advance: 5.418457, offset: 52 },
+        StaticGlyph { codepoint: 0x2c, left: 1, top: -2, width: 3, height: 4, advance: 5.418457, offset: 58 },
+        StaticGlyph { codepoint: 0x2d, left: 1, top: -3, width: 3, height: 1, advance: 5.418457, offset: 61 },
+        StaticGlyph { codepoint: 0x2e, left: 2, top: -2, width: 2, height: 2, advance: 5.418457, offset: 64 },
+        StaticGlyph { codepoint: 0x2f, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 66 },
+        StaticGlyph { codepoint: 0x30, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 71 },
+        StaticGlyph { codepoint: 0x31, left: 1, top: -7, width: 4, height: 7, advance: 5.418457, offset: 76 },
+        StaticGlyph { codepoint: 0x32, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 80 },
+        StaticGlyph { codepoint: 0x33, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 85 },
+        StaticGlyph { codepoint: 0x34, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 90 },
+        StaticGlyph { codepoint: 0x35, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 95 },
+        StaticGlyph { codepoint: 0x36, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 100 },
+        StaticGlyph { codepoint: 0x37, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 105 },
+        StaticGlyph { codepoint: 0x38, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 110 },
+        StaticGlyph { codepoint: 0x39, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 115 },
+        StaticGlyph { codepoint: 0x3a, left: 2, top: -5, width: 2, height: 5, advance: 5.418457, offset: 120 },
+        StaticGlyph { codepoint: 0x3b, left: 1, top: -5, width: 3, height: 7, advance: 5.418457, offset: 122 },
+        StaticGlyph { codepoint: 0x3c, left: 0, top: -6, width: 6, height: 6, advance: 5.418457, offset: 125 },
+        StaticGlyph { codepoint: 0x3d, left: 0, top: -5, width: 6, height: 4, advance: 5.418457, offset: 131 },
+        StaticGlyph { codepoint: 0x3e, left: 0, top: -6, width: 6, height: 6, advance: 5.418457, offset: 137 },
+        StaticGlyph { codepoint: 0x3f, left: 1, top: -7, width: 4, height: 7, advance: 5.418457, offset: 143 },
+        StaticGlyph { codepoint: 0x40, left: 0, top: -7, width: 6, height: 9, advance: 5.418457, offset: 147 },
+        StaticGlyph { codepoint: 0x41, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 159 },
+        StaticGlyph { codepoint: 0x42, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 165 },
+        StaticGlyph { codepoint: 0x43, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 170 },
+        StaticGlyph { codepoint: 0x44, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 175 },
+        StaticGlyph { codepoint: 0x45, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 180 },
+        StaticGlyph { codepoint: 0x46, left: 1, top: -7, width: 4, height: 7, advance: 5.418457, offset: 185 },
+        StaticGlyph { codepoint: 0x47, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 189 },
+        StaticGlyph { codepoint: 0x48, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 194 },
+        StaticGlyph { codepoint: 0x49, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 199 },
+        StaticGlyph { codepoint: 0x4a, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 204 },
+        StaticGlyph { codepoint: 0x4b, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 209 },
+        StaticGlyph { codepoint: 0x4c, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 215 },
+        StaticGlyph { codepoint: 0x4d, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 221 },
+        StaticGlyph { codepoint: 0x4e, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 227 },
+        StaticGlyph { codepoint: 0x4f, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 232 },
+        StaticGlyph { codepoint: 0x50, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 237 },
+        StaticGlyph { codepoint: 0x51, left: 0, top: -7, width: 5, height: 9, advance: 5.418457, offset: 243 },
+        StaticGlyph { codepoint: 0x52, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 253 },
+        StaticGlyph { codepoint: 0x53, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 259 },
+        StaticGlyph { codepoint: 0x54, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 264 },
+        StaticGlyph { codepoint: 0x55, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 270 },
+        StaticGlyph { codepoint: 0x56, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 275 },
+        StaticGlyph { codepoint: 0x57, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 281 },
+        StaticGlyph { codepoint: 0x58, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 287 },
+        StaticGlyph { codepoint: 0x59, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 293 },
+        StaticGlyph { codepoint: 0x5a, left: 0, top: -7, width: 6, height: 7, advance: 5.418457, offset: 299 },
+        StaticGlyph { codepoint: 0x5b, left: 2, top: -7, width: 2, height: 9, advance: 5.418457, offset: 305 },
+        StaticGlyph { codepoint: 0x5c, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 309 },
+        StaticGlyph { codepoint: 0x5d, left: 1, top: -7, width: 3, height: 9, advance: 5.418457, offset: 314 },
+        StaticGlyph { codepoint: 0x5e, left: 0, top: -7, width: 6, height: 3, advance: 5.418457, offset: 320 },
+        StaticGlyph { codepoint: 0x5f, left: 0, top: 1, width: 6, height: 2, advance: 5.418457, offset: 326 },
+        StaticGlyph { codepoint: 0x60, left: 1, top: -8, width: 3, height: 3, advance: 5.418457, offset: 332 },
+        StaticGlyph { codepoint: 0x61, left: 0, top: -6, width: 5, height: 7, advance: 5.418457, offset: 335 },
+        StaticGlyph { codepoint: 0x62, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 340 },
+        StaticGlyph { codepoint: 0x63, left: 0, top: -6, width: 5, height: 7, advance: 5.418457, offset: 345 },
+        StaticGlyph { codepoint: 0x64, left: 0, top: -7, width: 5, height: 8, advance: 5.418457, offset: 350 },
+        StaticGlyph { codepoint: 0x65, left: 0, top: -6, width: 5, height: 7, advance: 5.418457, offset: 355 },
+        StaticGlyph { codepoint: 0x66, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 360 },
+        StaticGlyph { codepoint: 0x67, left: 0, top: -6, width: 5, height: 8, advance: 5.418457, offset: 365 },
+        StaticGlyph { codepoint: 0x68, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 370 },
+        StaticGlyph { codepoint: 0x69, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 375 },
+        StaticGlyph { codepoint: 0x6a, left: 0, top: -7, width: 4, height: 9, advance: 5.418457, offset: 380 },
+        StaticGlyph { codepoint: 0x6b, left: 1, top: -7, width: 5, height: 7, advance: 5.418457, offset: 388 },
+        StaticGlyph { codepoint: 0x6c, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 393 },
+        StaticGlyph { codepoint: 0x6d, left: 0, top: -6, width: 5, height: 6, advance: 5.418457, offset: 398 },
+        StaticGlyph { codepoint: 0x6e, left: 0, top: -6, width: 5, height: 6, advance: 5.418457, offset: 403 },
+        StaticGlyph { codepoint: 0x6f, left: 0, top: -6, width: 5, height: 7, advance: 5.418457, offset: 408 },
+        StaticGlyph { codepoint: 0x70, left: 0, top: -6, width: 5, height: 8, advance: 5.418457, offset: 413 },
+        StaticGlyph { codepoint: 0x71, left: 0, top: -6, width: 5, height: 8, advance: 5.418457, offset: 418 },
+        StaticGlyph { codepoint: 0x72, left: 1, top: -6, width: 5, height: 6, advance: 5.418457, offset: 423 },
+        StaticGlyph { codepoint: 0x73, left: 0, top: -6, width: 5, height: 7, advance: 5.418457, offset: 428 },
+        StaticGlyph { codepoint: 0x74, left: 0, top: -7, width: 5, height: 7, advance: 5.418457, offset: 433 },
+        StaticGlyph { codepoint: 0x75, left: 0, top: -5, width: 5, height: 6, advance: 5.418457, offset: 438 },
+        StaticGlyph { codepoint: 0x76, left: 0, top: -5, width: 5, height: 5, advance: 5.418457, offset: 443 },
+        StaticGlyph { codepoint: 0x77, left: 0, top: -5, width: 6, height: 5, advance: 5.418457, offset: 448 },
+        StaticGlyph { codepoint: 0x78, left: 0, top: -5, width: 6, height: 5, advance: 5.418457, offset: 454 },
+        StaticGlyph { codepoint: 0x79, left: 0, top: -5, width: 6, height: 7, advance: 5.418457, offset: 460 },
+        StaticGlyph { codepoint: 0x7a, left: 0, top: -5, width: 5, height: 5, advance: 5.418457, offset: 466 },
+        StaticGlyph { codepoint: 0x7b, left: 0, top: -7, width: 5, height: 9, advance: 5.418457, offset: 471 },
+        StaticGlyph { codepoint: 0x7c, left: 2, top: -7, width: 2, height: 10, advance: 5.418457, offset: 481 },
+        StaticGlyph { codepoint: 0x7d, left: 0, top: -7, width: 5, height: 9, advance: 5.418457, offset: 485 },
+        StaticGlyph { codepoint: 0x7e, left: 0, top: -4, width: 6, height: 2, advance: 5.418457, offset: 495 },
+    ],
+    data: &[
+        0x5f, 0x00, 0x07, 0x06, 0x07, 0x74, 0x7c, 0x7f, 0x7c, 0x37, 0x04, 0x00, 0x4e, 0xff, 0x52, 0x72,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x16, 0x1f, 0x3e, 0x78, 0x78, 0x20, 0x70, 0x6f, 0x59, 0x71, 0x70,
+        0x10, 0x07, 0x00, 0x00, 0x7e, 0xc3, 0x00, 0x00, 0x00, 0x81, 0xff, 0x3c, 0x00, 0x00, 0x00, 0x00,
+        0x0e, 0x1f, 0x06, 0x0a, 0x08, 0x0c, 0x3e, 0x0c, 0x0c, 0x00, 0x00, 0x07, 0x02, 0x01, 0x01, 0x01,
+        0x03, 0x02, 0x80, 0xe0, 0x38, 0x0e, 0x03, 0x3c, 0x7f, 0x49, 0x6f, 0x3e, 0x43, 0x7f, 0x7f, 0x40,
+        0x42, 0x63, 0x71, 0x5f, 0x46, 0x40, 0x4b, 0x49, 0x7f, 0x76, 0x30, 0x3c, 0x36, 0x7f, 0x30, 0x40,
+        0x4f, 0x4f, 0x7b, 0x38, 0x3c, 0x7e, 0x4f, 0x6d, 0x79, 0x01, 0x43, 0x73, 0x1f, 0x03, 0x30, 0x7f,
+        0x49, 0x7f, 0x76, 0x0e, 0x5f, 0x51, 0x7f, 0x3e, 0x1b, 0x13, 0x00, 0x3b, 0x13, 0x0c, 0x0c, 0x1c,
+        0x16, 0x32, 0x00, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x00, 0x12, 0x16, 0x1e, 0x0c, 0x0c, 0x00, 0x03,
+        0x79, 0x0f, 0x06, 0x7c, 0xc6, 0xfa, 0xee, 0xfe, 0x38, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x60,
+        0x7c, 0x37, 0x3f, 0x78, 0x00, 0x7e, 0x7f, 0x4b, 0x5f, 0x7e, 0x1c, 0x7e, 0x43, 0x41, 0x43, 0x7f,
+        0x7f, 0x43, 0x67, 0x3e, 0x00, 0x7f, 0x4b, 0x4b, 0x49, 0x7f, 0x0b, 0x0b, 0x0b, 0x3c, 0x7e, 0x43,
+        0x59, 0x7b, 0x7f, 0x7f, 0x08, 0x08, 0x7f, 0x00, 0x43, 0x7f, 0x43, 0x41, 0x40, 0x41, 0x43, 0x7f,
+        0x1e, 0x7f, 0x7f, 0x1c, 0x36, 0x63, 0x00, 0x00, 0x7f, 0x40, 0x40, 0x40, 0x00, 0x7f, 0x7f, 0x1c,
+        0x0e, 0x7f, 0x00, 0x7f, 0x7f, 0x1e, 0x70, 0x7f, 0x3c, 0x7f, 0x41, 0x63, 0x7e, 0x00, 0x7f, 0x1b,
+        0x1b, 0x0e, 0x00, 0x3c, 0x7f, 0x41, 0xe3, 0xbe, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7f, 0x7f, 0x1b,
+        0x3f, 0x6e, 0x40, 0x46, 0x4f, 0x49, 0x5b, 0x73, 0x01, 0x03, 0x7f, 0x03, 0x03, 0x00, 0x3f, 0x7f,
+        0x40, 0x60, 0x7f, 0x03, 0x3e, 0x70, 0x7c, 0x0f, 0x00, 0x3f, 0x78, 0x3c, 0x7c, 0x7f, 0x03, 0x41,
+        0x77, 0x1c, 0x3e, 0x63, 0x00, 0x03, 0x06, 0x7c, 0x0e, 0x03, 0x00, 0x40, 0x63, 0x5b, 0x4f, 0x43,
+        0x00, 0xff, 0x81, 0x01, 0x01, 0x01, 0x07, 0x3c, 0xf0, 0x80, 0x81, 0xff, 0xff, 0x00, 0x01, 0x00,
+        0x04, 0x06, 0x03, 0x03, 0x04, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x00, 0x02, 0x06, 0x00, 0x10,
+        0x3a, 0x2a, 0x3e, 0x3e, 0x00, 0x7f, 0x44, 0x6c, 0x7c, 0x00, 0x3e, 0x22, 0x22, 0x22, 0x38, 0x7c,
+        0x44, 0x7f, 0x7f, 0x1c, 0x3e, 0x2a, 0x2a, 0x2e, 0x00, 0x04, 0x7f, 0x05, 0x05, 0x1c, 0xbe, 0xa2,
+        0xfe, 0x7e, 0x00, 0x7f, 0x04, 0x7c, 0x7c, 0x00, 0x44, 0x7d, 0x7d, 0x40, 0x00, 0x04, 0xfd, 0xfd,
+        0x00, 0x01, 0x01, 0x00, 0x7f, 0x18, 0x3c, 0x64, 0x00, 0x01, 0x01, 0x7f, 0x40, 0x40, 0x3e, 0x3e,
+        0x3e, 0x02, 0x3e, 0x00, 0x3e, 0x02, 0x3e, 0x3e, 0x1c, 0x3e, 0x22, 0x36, 0x3e, 0x7c, 0xfe, 0x22,
+        0x36, 0x3e, 0x1c, 0x3e, 0x22, 0x7e, 0xfe, 0x3e, 0x3e, 0x02, 0x02, 0x00, 0x00, 0x2e, 0x2a, 0x3a,
+        0x32, 0x04, 0x04, 0x7f, 0x44, 0x44, 0x00, 0x1f, 0x10, 0x1f, 0x1f, 0x01, 0x0f, 0x18, 0x1e, 0x03,
+        0x0f, 0x1c, 0x0e, 0x1c, 0x1f, 0x01, 0x11, 0x1b, 0x0e, 0x1f, 0x11, 0x00, 0x01, 0x4f, 0x7c, 0x1e,
+        0x03, 0x00, 0x00, 0x19, 0x1d, 0x17, 0x11, 0x00, 0x10, 0xff, 0x83, 0x01, 0x00, 0x00, 0x00, 0x01,
+        0x01, 0xff, 0x00, 0x01, 0x00, 0x00, 0x81, 0xff, 0x18, 0x10, 0x00, 0x01, 0x01, 0x00, 0x00, 0x02,
+        0x03, 0x03, 0x02, 0x02, 0x00,
+    ],
+};
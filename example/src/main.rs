@@ -1,5 +1,8 @@
 extern crate mono_display;
 
+mod dejavu_sans_16;
+mod dejavu_sans_mono_9;
+
 use mono_display::gfx;
 use mono_display::DisplayDriver;
 use std::env;
@@ -18,9 +21,21 @@ fn create_driver() -> mono_display::Result<mono_display::ssd1306::Ssd1306> {
 
 fn show_demo() -> mono_display::Result<()> {
     let mut driver = create_driver()?;
+
+    // Embedded fonts are used unless BDF fonts are specified on the command
+    // line.
     let args: Vec<String> = env::args().collect();
-    let font16 = gfx::Font::load_bdf(args[1].as_str())?;
-    let font8 = gfx::Font::load_bdf(args[2].as_str())?;
+    let (font16, font8) = if args.len() > 2 {
+        (
+            gfx::Font::load_bdf(args[1].as_str())?,
+            gfx::Font::load_bdf(args[2].as_str())?,
+        )
+    } else {
+        (
+            gfx::Font::from_static(&dejavu_sans_16::DEJAVU_SANS_16),
+            gfx::Font::from_static(&dejavu_sans_mono_9::DEJAVU_SANS_MONO_9),
+        )
+    };
     for i in 0..500 {
         let mut canvas = gfx::Canvas::new(driver.get_frame());
 
@@ -28,10 +43,14 @@ fn show_demo() -> mono_display::Result<()> {
         canvas.draw_text(text_pos, &font16, "ABCD abcdefghijklmnopqrstuvwxyz", gfx::Color::Light);
 
         let text_2 = "ABCDEFGHJIKLMNOPQRSTUVWXYZ abcdefghjiklmnopqrstuvwxyz";
-        let text_pos_2 = text_pos.add_xy(0, 16);
-        let text_rect_2 = canvas.get_text_rect(text_pos_2, &font8, text_2);
-        canvas.draw_rect(text_rect_2, gfx::Color::Light);
-        canvas.draw_text(text_pos_2, &font8, text_2, gfx::Color::Dark);
+        let style = gfx::TextStyle {
+            inverse: true,
+            ..gfx::TextStyle::default()
+        };
+        let width = canvas.clip().size.width;
+        canvas.draw_view(gfx::Rect::xywh(0, 16, width, 16), |view| {
+            view.draw_styled_text(text_pos, &font8, text_2, style, gfx::Color::Light);
+        });
 
         driver.show_frame(canvas.take_frame());
 
@@ -0,0 +1,123 @@
+// Converts a font file to Rust source with a gfx::StaticFont, so the font
+// can be embedded in the binary.
+//
+// Usage: font2rs <font file> <NAME> [--size <pixels>] [--threshold <0-255>]
+//                [--chars <ranges>]
+//
+// --size is required for TTF fonts, --threshold is used for TTF fonts to
+// convert anti-aliased glyphs to monochrome. --chars is a comma-separated
+// list of hexadecimal codepoint ranges, e.g. 20-7e,410-44f. By default all
+// glyphs are included for bitmap fonts and printable ASCII for TTF fonts.
+// OpenType fonts with CFF outlines (.otf) are not supported.
+
+extern crate mono_display;
+
+use mono_display::gfx;
+use std::env;
+
+fn load_font(
+    filename: &str,
+    size: Option<usize>,
+    options: gfx::TtfRenderOptions,
+) -> mono_display::Result<gfx::Font> {
+    let extension = filename.rsplit('.').next().unwrap_or("").to_lowercase();
+    match extension.as_str() {
+        "bdf" => gfx::Font::load_bdf(filename),
+        "pcf" => gfx::Font::load_pcf(filename),
+        "psf" | "psfu" => gfx::Font::load_psf(filename),
+        "c" => gfx::Font::load_u8g2(filename),
+        "h" => gfx::Font::load_adafruit_gfx(filename),
+        "ttf" => match size {
+            Some(size) => gfx::Font::load_ttf(filename, size, options),
+            None => Err(mono_display::Error::new(
+                "--size is required for TTF fonts.",
+            )),
+        },
+        _ => Err(mono_display::Error::from_string(format!(
+            "Unknown font format: {}",
+            filename
+        ))),
+    }
+}
+
+fn parse_chars(ranges: &str) -> mono_display::Result<Vec<char>> {
+    let mut result = Vec::new();
+    for range in ranges.split(',') {
+        let mut parts = range.splitn(2, '-');
+        let first = parts.next().unwrap_or("");
+        let last = parts.next().unwrap_or(first);
+        let error =
+            || mono_display::Error::from_string(format!("Invalid character range: {}", range));
+        let parse = |s: &str| u32::from_str_radix(s.trim(), 16).map_err(|_| error());
+        let (first, last) = (parse(first)?, parse(last)?);
+        if first > last {
+            return Err(error());
+        }
+        for c in first..=last {
+            if let Some(c) = std::char::from_u32(c) {
+                result.push(c);
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn run() -> mono_display::Result<()> {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 3 {
+        return Err(mono_display::Error::new(
+            "Usage: font2rs <font file> <NAME> [--size <pixels>] [--threshold <0-255>] \
+             [--chars <ranges>]",
+        ));
+    }
+
+    let mut size = None;
+    let mut options = gfx::TtfRenderOptions::default();
+    let mut chars = None;
+    let mut i = 3;
+    while i < args.len() {
+        let value = args.get(i + 1).map(|v| v.as_str()).unwrap_or("");
+        match args[i].as_str() {
+            "--size" => {
+                size = Some(value.parse::<usize>().map_err(|_| {
+                    mono_display::Error::from_string(format!("Invalid size: {}", value))
+                })?)
+            }
+            "--threshold" => {
+                options.threshold = value.parse::<u8>().map_err(|_| {
+                    mono_display::Error::from_string(format!("Invalid threshold: {}", value))
+                })?
+            }
+            "--chars" => chars = Some(parse_chars(value)?),
+            arg => {
+                return Err(mono_display::Error::from_string(format!(
+                    "Unknown argument: {}",
+                    arg
+                )))
+            }
+        }
+        i += 2;
+    }
+
+    let font = load_font(args[1].as_str(), size, options)?;
+    let chars = match chars {
+        Some(chars) => chars,
+        None if size.is_some() => parse_chars("20-7e")?,
+        None => font.chars(),
+    };
+
+    println!(
+        "// Generated by font2rs from {}.\n",
+        args[1].rsplit('/').next().unwrap_or("")
+    );
+    print!("{}", font.to_rust_source(args[2].as_str(), &chars[..]));
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("{}", e.msg);
+        std::process::exit(-1);
+    }
+}
@@ -0,0 +1,1128 @@
+// Readers for bitmap font formats other than BDF: PCF, PSF and the C
+// source formats used by u8g2 and Adafruit GFX libraries.
+
+use std::cmp;
+use std::collections::HashMap;
+use std::str;
+
+use super::Error;
+use super::Result;
+
+pub struct BitmapGlyph {
+    pub codepoint: u32,
+
+    // Position of the bitmap relative to the pen, with y pointing down.
+    pub left: i16,
+    pub top: i16,
+    pub width: usize,
+    pub height: usize,
+
+    pub advance: i16,
+
+    // Pixels in row-major order.
+    pub pixels: Vec<bool>,
+}
+
+// Font metrics are set only when the format specifies them.
+#[derive(Default)]
+pub struct BitmapFont {
+    pub glyphs: Vec<BitmapGlyph>,
+    pub ascent: Option<i16>,
+    pub descent: Option<i16>,
+    pub line_height: Option<i16>,
+    pub pixel_size: Option<i16>,
+    pub default_char: Option<u32>,
+}
+
+fn get_u16_le(data: &[u8], offset: usize) -> Option<u16> {
+    Some(*data.get(offset)? as u16 | (*data.get(offset + 1)? as u16) << 8)
+}
+
+fn get_u32_le(data: &[u8], offset: usize) -> Option<u32> {
+    Some(get_u16_le(data, offset)? as u32 | (get_u16_le(data, offset + 2)? as u32) << 16)
+}
+
+fn get_u16_be(data: &[u8], offset: usize) -> Option<u16> {
+    Some((*data.get(offset)? as u16) << 8 | *data.get(offset + 1)? as u16)
+}
+
+fn get_u32_be(data: &[u8], offset: usize) -> Option<u32> {
+    Some((get_u16_be(data, offset)? as u32) << 16 | get_u16_be(data, offset + 2)? as u32)
+}
+
+// Returns true if bit |index| is set in |data|, counting from the most
+// significant bit of the first byte.
+fn get_bit_msb(data: &[u8], index: usize) -> bool {
+    match data.get(index / 8) {
+        Some(b) => b & (0x80 >> (index % 8)) != 0,
+        None => false,
+    }
+}
+
+// PCF fonts are compiled X11 fonts. Tables can be stored in either byte
+// order and bitmaps can be padded in different ways, the format of each
+// table is specified in the table itself.
+
+const PCF_PROPERTIES: u32 = 1 << 0;
+const PCF_METRICS: u32 = 1 << 2;
+const PCF_BITMAPS: u32 = 1 << 3;
+const PCF_BDF_ENCODINGS: u32 = 1 << 5;
+
+const PCF_BYTE_ORDER_MSB: u32 = 1 << 2;
+const PCF_BIT_ORDER_MSB: u32 = 1 << 3;
+const PCF_COMPRESSED_METRICS: u32 = 0x100;
+
+struct PcfTable<'a> {
+    format: u32,
+    data: &'a [u8],
+}
+
+impl<'a> PcfTable<'a> {
+    fn u16(&self, offset: usize) -> Option<u16> {
+        if self.format & PCF_BYTE_ORDER_MSB != 0 {
+            get_u16_be(self.data, offset)
+        } else {
+            get_u16_le(self.data, offset)
+        }
+    }
+
+    fn i16(&self, offset: usize) -> Option<i16> {
+        self.u16(offset).map(|v| v as i16)
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        if self.format & PCF_BYTE_ORDER_MSB != 0 {
+            get_u32_be(self.data, offset)
+        } else {
+            get_u32_le(self.data, offset)
+        }
+    }
+}
+
+struct PcfMetrics {
+    left_bearing: i16,
+    right_bearing: i16,
+    width: i16,
+    ascent: i16,
+    descent: i16,
+}
+
+fn get_pcf_table<'a>(data: &'a [u8], type_: u32) -> Option<PcfTable<'a>> {
+    let count = get_u32_le(data, 4)? as usize;
+    for i in 0..count {
+        let record = 8 + i * 16;
+        if get_u32_le(data, record)? == type_ {
+            let size = get_u32_le(data, record + 8)? as usize;
+            let offset = get_u32_le(data, record + 12)? as usize;
+            let data = data.get(offset..(offset + size))?;
+            return Some(PcfTable {
+                // Format is always stored in LSB first byte order.
+                format: get_u32_le(data, 0)?,
+                data,
+            });
+        }
+    }
+    None
+}
+
+fn get_pcf_properties(table: &PcfTable) -> Option<HashMap<String, i32>> {
+    let count = table.u32(4)? as usize;
+    let strings_offset = 8 + count * 9 + (4 - count % 4) % 4 + 4;
+    let strings = table.data.get(strings_offset..)?;
+    let get_string = |offset: usize| -> Option<&str> {
+        let s = strings.get(offset..)?;
+        let end = s.iter().position(|&c| c == 0)?;
+        str::from_utf8(&s[..end]).ok()
+    };
+
+    let mut result = HashMap::new();
+    for i in 0..count {
+        let record = 8 + i * 9;
+        let is_string = *table.data.get(record + 4)? != 0;
+        if !is_string {
+            let name = get_string(table.u32(record)? as usize)?;
+            result.insert(name.to_string(), table.u32(record + 5)? as i32);
+        }
+    }
+    Some(result)
+}
+
+fn get_pcf_metrics(table: &PcfTable) -> Option<Vec<PcfMetrics>> {
+    let mut result = Vec::new();
+    if table.format & PCF_COMPRESSED_METRICS != 0 {
+        let get = |offset: usize| table.data.get(offset).map(|&v| v as i16 - 0x80);
+        for i in 0..(table.u16(4)? as usize) {
+            let m = 6 + i * 5;
+            result.push(PcfMetrics {
+                left_bearing: get(m)?,
+                right_bearing: get(m + 1)?,
+                width: get(m + 2)?,
+                ascent: get(m + 3)?,
+                descent: get(m + 4)?,
+            });
+        }
+    } else {
+        for i in 0..(table.u32(4)? as usize) {
+            let m = 8 + i * 12;
+            result.push(PcfMetrics {
+                left_bearing: table.i16(m)?,
+                right_bearing: table.i16(m + 2)?,
+                width: table.i16(m + 4)?,
+                ascent: table.i16(m + 6)?,
+                descent: table.i16(m + 8)?,
+            });
+        }
+    }
+    Some(result)
+}
+
+fn get_pcf_encodings(table: &PcfTable) -> Option<(Vec<(u32, usize)>, u32)> {
+    let min_byte2 = table.u16(4)? as u32;
+    let max_byte2 = table.u16(6)? as u32;
+    let min_byte1 = table.u16(8)? as u32;
+    let max_byte1 = table.u16(10)? as u32;
+    let default_char = table.u16(12)? as u32;
+
+    let mut result = Vec::new();
+    let mut offset = 14;
+    for byte1 in min_byte1..(max_byte1 + 1) {
+        for byte2 in min_byte2..(max_byte2 + 1) {
+            let index = table.u16(offset)?;
+            offset += 2;
+            if index != 0xffff {
+                result.push(((byte1 << 8) | byte2, index as usize));
+            }
+        }
+    }
+    Some((result, default_char))
+}
+
+// Returns bitmap of glyph |index| as a row-major list of pixels.
+fn get_pcf_bitmap(
+    table: &PcfTable,
+    index: usize,
+    width: usize,
+    height: usize,
+) -> Option<Vec<bool>> {
+    let count = table.u32(4)? as usize;
+    let offset = table.u32(8 + index * 4)? as usize;
+    let data = table.data.get((8 + count * 4 + 16 + offset)..)?;
+
+    // Rows are padded to 1, 2, 4 or 8 bytes.
+    let pad = 1 << (table.format & 3);
+    let row_bytes = ((width + 7) / 8 + pad - 1) / pad * pad;
+    let scan_unit = 1 << ((table.format >> 4) & 3);
+    let swap_bytes =
+        (table.format & PCF_BYTE_ORDER_MSB != 0) != (table.format & PCF_BIT_ORDER_MSB != 0);
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let row = data.get((y * row_bytes)..((y + 1) * row_bytes))?;
+        for x in 0..width {
+            let mut byte = x / 8;
+            if swap_bytes {
+                byte = byte - byte % scan_unit + scan_unit - 1 - byte % scan_unit;
+            }
+            let bit = if table.format & PCF_BIT_ORDER_MSB != 0 {
+                0x80 >> (x % 8)
+            } else {
+                1 << (x % 8)
+            };
+            pixels.push(row.get(byte)? & bit != 0);
+        }
+    }
+    Some(pixels)
+}
+
+fn parse_pcf_internal(data: &[u8]) -> Option<BitmapFont> {
+    let metrics = get_pcf_metrics(&get_pcf_table(data, PCF_METRICS)?)?;
+    let bitmaps = get_pcf_table(data, PCF_BITMAPS)?;
+    let (encodings, default_char) = get_pcf_encodings(&get_pcf_table(data, PCF_BDF_ENCODINGS)?)?;
+    let properties = get_pcf_table(data, PCF_PROPERTIES)
+        .and_then(|t| get_pcf_properties(&t))
+        .unwrap_or_default();
+
+    let mut glyphs = Vec::with_capacity(encodings.len());
+    for (codepoint, index) in encodings {
+        let m = metrics.get(index)?;
+        let width = cmp::max(0, m.right_bearing - m.left_bearing) as usize;
+        let height = cmp::max(0, m.ascent + m.descent) as usize;
+        glyphs.push(BitmapGlyph {
+            codepoint,
+            left: m.left_bearing,
+            top: -m.ascent,
+            width,
+            height,
+            advance: m.width,
+            pixels: get_pcf_bitmap(&bitmaps, index, width, height)?,
+        });
+    }
+
+    let get_property = |name: &str| properties.get(name).map(|&v| v as i16);
+    Some(BitmapFont {
+        glyphs,
+        ascent: get_property("FONT_ASCENT"),
+        descent: get_property("FONT_DESCENT"),
+        line_height: None,
+        pixel_size: get_property("PIXEL_SIZE"),
+        default_char: Some(default_char),
+    })
+}
+
+pub fn parse_pcf(data: &[u8]) -> Result<BitmapFont> {
+    if !data.starts_with(b"\x01fcp") {
+        return Err(Error::new(
+            "Not a PCF font. Compressed fonts are not supported.",
+        ));
+    }
+    parse_pcf_internal(data).ok_or_else(|| Error::new("Invalid PCF font."))
+}
+
+// PSF fonts are Linux console fonts. All glyphs have the same size and
+// there is no baseline, it's guessed from the bottom of the 'H' glyph.
+
+const PSF1_MODE_512: u8 = 0x01;
+const PSF1_MODE_HAS_TABLE: u8 = 0x02;
+const PSF2_HAS_UNICODE_TABLE: u32 = 0x01;
+
+// Splits unicode table of a PSF font into a list of codepoints for each
+// glyph. Sequences of combining characters are skipped.
+fn get_psf_codepoints(table: &[u8], num_glyphs: usize, psf2: bool) -> Option<Vec<Vec<u32>>> {
+    let mut result = Vec::with_capacity(num_glyphs);
+    let mut pos = 0;
+    for _ in 0..num_glyphs {
+        let mut codepoints = Vec::new();
+        let mut in_sequence = false;
+        loop {
+            if psf2 {
+                let lead = *table.get(pos)?;
+                if lead == 0xff {
+                    pos += 1;
+                    break;
+                }
+                if lead == 0xfe {
+                    in_sequence = true;
+                    pos += 1;
+                    continue;
+                }
+                let len = match lead {
+                    0x00..=0x7f => 1,
+                    0xc0..=0xdf => 2,
+                    0xe0..=0xef => 3,
+                    _ => 4,
+                };
+                let c = str::from_utf8(table.get(pos..(pos + len))?)
+                    .ok()
+                    .and_then(|s| s.chars().next());
+                pos += len;
+                if let (Some(c), false) = (c, in_sequence) {
+                    codepoints.push(c as u32);
+                }
+            } else {
+                let c = get_u16_le(table, pos)?;
+                pos += 2;
+                if c == 0xffff {
+                    break;
+                }
+                if c == 0xfffe {
+                    in_sequence = true;
+                } else if !in_sequence {
+                    codepoints.push(c as u32);
+                }
+            }
+        }
+        result.push(codepoints);
+    }
+    Some(result)
+}
+
+fn parse_psf_internal(data: &[u8]) -> Option<BitmapFont> {
+    let (num_glyphs, glyph_size, width, height, bitmaps_offset, table) =
+        if data.starts_with(&[0x36, 0x04]) {
+            let mode = *data.get(2)?;
+            let height = *data.get(3)? as usize;
+            let num_glyphs = if mode & PSF1_MODE_512 != 0 { 512 } else { 256 };
+            let table_offset = 4 + num_glyphs * height;
+            let table = if mode & PSF1_MODE_HAS_TABLE != 0 {
+                Some(get_psf_codepoints(
+                    data.get(table_offset..)?,
+                    num_glyphs,
+                    false,
+                )?)
+            } else {
+                None
+            };
+            (num_glyphs, height, 8, height, 4, table)
+        } else {
+            let header_size = get_u32_le(data, 8)? as usize;
+            let flags = get_u32_le(data, 12)?;
+            let num_glyphs = get_u32_le(data, 16)? as usize;
+            let glyph_size = get_u32_le(data, 20)? as usize;
+            let height = get_u32_le(data, 24)? as usize;
+            let width = get_u32_le(data, 28)? as usize;
+            let table_offset = header_size + num_glyphs * glyph_size;
+            let table = if flags & PSF2_HAS_UNICODE_TABLE != 0 {
+                Some(get_psf_codepoints(
+                    data.get(table_offset..)?,
+                    num_glyphs,
+                    true,
+                )?)
+            } else {
+                None
+            };
+            (num_glyphs, glyph_size, width, height, header_size, table)
+        };
+
+    let row_bytes = (width + 7) / 8;
+    let mut glyphs = Vec::new();
+    let mut baseline = None;
+    for i in 0..num_glyphs {
+        let offset = bitmaps_offset + i * glyph_size;
+        let bitmap = data.get(offset..(offset + glyph_size))?;
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(get_bit_msb(bitmap, y * row_bytes * 8 + x));
+            }
+        }
+
+        let codepoints = match table {
+            Some(ref table) => table[i].clone(),
+            None => vec![i as u32],
+        };
+        if codepoints.contains(&('H' as u32)) {
+            baseline = (0..height)
+                .rev()
+                .find(|&y| pixels[(y * width)..((y + 1) * width)].iter().any(|&p| p))
+                .map(|y| y as i16 + 1);
+        }
+        for codepoint in codepoints {
+            glyphs.push(BitmapGlyph {
+                codepoint,
+                left: 0,
+                top: 0,
+                width,
+                height,
+                advance: width as i16,
+                pixels: pixels.clone(),
+            });
+        }
+    }
+
+    let ascent = baseline.unwrap_or(height as i16 * 3 / 4);
+    for glyph in glyphs.iter_mut() {
+        glyph.top = -ascent;
+    }
+    Some(BitmapFont {
+        glyphs,
+        ascent: Some(ascent),
+        descent: Some(height as i16 - ascent),
+        line_height: Some(height as i16),
+        pixel_size: Some(height as i16),
+        default_char: None,
+    })
+}
+
+pub fn parse_psf(data: &[u8]) -> Result<BitmapFont> {
+    if !data.starts_with(&[0x36, 0x04]) && !data.starts_with(&[0x72, 0xb5, 0x4a, 0x86]) {
+        return Err(Error::new("Not a PSF font."));
+    }
+    parse_psf_internal(data).ok_or_else(|| Error::new("Invalid PSF font."))
+}
+
+// u8g2 fonts are run-length encoded bitstreams. They are distributed as C
+// source, see parse_c_string().
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    // Bits are read starting from the least significant bit of each byte.
+    fn read(&mut self, bits: u8) -> Option<u32> {
+        let mut result = 0;
+        for i in 0..bits {
+            let byte = *self.data.get(self.pos / 8)?;
+            if byte & (1 << (self.pos % 8)) != 0 {
+                result |= 1 << i;
+            }
+            self.pos += 1;
+        }
+        Some(result)
+    }
+
+    fn read_signed(&mut self, bits: u8) -> Option<i16> {
+        if bits == 0 {
+            return Some(0);
+        }
+        Some(self.read(bits)? as i16 - (1 << (bits - 1)))
+    }
+}
+
+fn decode_u8g2_glyph(header: &[u8], codepoint: u32, data: &[u8]) -> Option<BitmapGlyph> {
+    let mut reader = BitReader { data, pos: 0 };
+    let width = reader.read(header[4])? as usize;
+    let height = reader.read(header[5])? as usize;
+    let x = reader.read_signed(header[6])?;
+    let y = reader.read_signed(header[7])?;
+    let advance = reader.read_signed(header[8])?;
+
+    let size = width * height;
+    let mut pixels = Vec::with_capacity(size);
+    while width > 0 && pixels.len() < size {
+        let zeros = reader.read(header[2])? as usize;
+        let ones = reader.read(header[3])? as usize;
+        loop {
+            let len = pixels.len();
+            pixels.resize(len + zeros, false);
+            pixels.resize(len + zeros + ones, true);
+            if reader.read(1)? == 0 {
+                break;
+            }
+        }
+    }
+    pixels.truncate(size);
+
+    Some(BitmapGlyph {
+        codepoint,
+        left: x,
+        top: -(height as i16) - y,
+        width,
+        height,
+        advance,
+        pixels,
+    })
+}
+
+fn parse_u8g2_internal(data: &[u8]) -> Option<BitmapFont> {
+    let header = data.get(..23)?;
+    let mut glyphs = Vec::new();
+
+    // Glyphs up to 0xFF have 1-byte codepoint and size.
+    let mut pos = 23;
+    loop {
+        let codepoint = *data.get(pos)? as u32;
+        let size = *data.get(pos + 1)? as usize;
+        if codepoint == 0 || size == 0 {
+            break;
+        }
+        glyphs.push(decode_u8g2_glyph(
+            header,
+            codepoint,
+            data.get((pos + 2)..)?,
+        )?);
+        pos += size;
+    }
+
+    // Other glyphs follow a lookup table, they have 2-byte codepoints.
+    let unicode_start = 23 + get_u16_be(header, 21)? as usize;
+    let offset = if unicode_start > pos {
+        get_u16_be(data, unicode_start)
+    } else {
+        None
+    };
+    if let Some(offset) = offset {
+        let mut pos = unicode_start + offset as usize;
+        loop {
+            let codepoint = match get_u16_be(data, pos) {
+                Some(0) | None => break,
+                Some(c) => c as u32,
+            };
+            let size = *data.get(pos + 2)? as usize;
+            if size == 0 {
+                break;
+            }
+            glyphs.push(decode_u8g2_glyph(
+                header,
+                codepoint,
+                data.get((pos + 3)..)?,
+            )?);
+            pos += size;
+        }
+    }
+
+    let ascent = cmp::max(header[13] as i8, header[15] as i8) as i16;
+    let descent = -(cmp::min(header[14] as i8, header[16] as i8) as i16);
+    Some(BitmapFont {
+        glyphs,
+        ascent: Some(ascent),
+        descent: Some(descent),
+        line_height: None,
+        pixel_size: Some(header[10] as i16),
+        default_char: None,
+    })
+}
+
+pub fn parse_u8g2(data: &[u8]) -> Result<BitmapFont> {
+    parse_u8g2_internal(data).ok_or_else(|| Error::new("Invalid u8g2 font."))
+}
+
+// Removes /* */ and // comments from C source.
+fn strip_c_comments(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+    loop {
+        let block = rest.find("/*");
+        let line = rest.find("//");
+        let (start, end_marker) = match (block, line) {
+            (Some(b), Some(l)) if l < b => (l, "\n"),
+            (Some(b), _) => (b, "*/"),
+            (None, Some(l)) => (l, "\n"),
+            (None, None) => break,
+        };
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match rest.find(end_marker) {
+            Some(end) => rest = &rest[(end + end_marker.len())..],
+            None => rest = "",
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+// Returns value of the first initializer in C source, i.e. the text between
+// the first '=' and the following ';', with comments removed.
+fn get_c_initializer(source: &str) -> Option<String> {
+    let source = strip_c_comments(source);
+    let start = source.find('=')? + 1;
+    let end = start + source[start..].find(';')?;
+    Some(source[start..end].to_string())
+}
+
+// Parses concatenated C string literals with octal escapes, as used for
+// u8g2 fonts.
+pub fn parse_c_string(source: &str) -> Result<Vec<u8>> {
+    let error = || Error::new("Failed to parse C string.");
+    let value = get_c_initializer(source).ok_or_else(error)?;
+    let mut result = Vec::new();
+    let mut bytes = value.bytes().peekable();
+    let mut in_string = false;
+    while let Some(c) = bytes.next() {
+        match (in_string, c) {
+            (_, b'"') => in_string = !in_string,
+            (false, _) => (),
+            (true, b'\\') => match bytes.next().ok_or_else(error)? {
+                d @ b'0'..=b'7' => {
+                    let mut v = (d - b'0') as u32;
+                    for _ in 0..2 {
+                        match bytes.peek() {
+                            Some(&d @ b'0'..=b'7') => {
+                                v = v * 8 + (d - b'0') as u32;
+                                bytes.next();
+                            }
+                            _ => break,
+                        }
+                    }
+                    result.push(v as u8);
+                }
+                b'n' => result.push(b'\n'),
+                b'r' => result.push(b'\r'),
+                b't' => result.push(b'\t'),
+                e => result.push(e),
+            },
+            (true, c) => result.push(c),
+        }
+    }
+    Ok(result)
+}
+
+// Returns all integer literals in C source, skipping identifiers.
+fn get_c_numbers(source: &str) -> Vec<i64> {
+    let mut result = Vec::new();
+    let mut token = String::new();
+    let mut negative = false;
+    for c in source.chars().chain(Some(' ')) {
+        if c.is_alphanumeric() || c == '_' {
+            token.push(c);
+            continue;
+        }
+        if !token.is_empty() {
+            let t = token.to_lowercase();
+            let value = match t.strip_prefix("0x") {
+                Some(hex) => i64::from_str_radix(hex, 16).ok(),
+                None => t.parse::<i64>().ok(),
+            };
+            if let Some(v) = value {
+                result.push(if negative { -v } else { v });
+            }
+            token.clear();
+        }
+        if !c.is_whitespace() {
+            negative = c == '-';
+        }
+    }
+    result
+}
+
+// Splits C source into the top-level brace-enclosed initializers.
+fn get_c_arrays(source: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in source.char_indices() {
+        match c {
+            '{' => {
+                if depth == 0 {
+                    start = i + 1;
+                }
+                depth += 1;
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    result.push(&source[start..i]);
+                }
+            }
+            _ => (),
+        }
+    }
+    result
+}
+
+// Adafruit GFX fonts are C headers with three arrays: bitmaps, glyphs and
+// the GFXfont struct.
+fn parse_adafruit_gfx_internal(source: &str) -> Option<BitmapFont> {
+    let source = strip_c_comments(source);
+    let arrays = get_c_arrays(&source);
+    let bitmaps: Vec<u8> = get_c_numbers(arrays.first()?)
+        .into_iter()
+        .map(|v| v as u8)
+        .collect();
+    let glyph_values = get_c_numbers(arrays.get(1)?);
+    let font_values = get_c_numbers(arrays.get(2)?);
+    let first = *font_values.first()? as u32;
+    let y_advance = *font_values.get(2)? as i16;
+
+    let mut glyphs = Vec::new();
+    for (i, g) in glyph_values.chunks(6).enumerate() {
+        if g.len() < 6 {
+            return None;
+        }
+        let offset = g[0] as usize * 8;
+        let width = g[1] as usize;
+        let height = g[2] as usize;
+        let pixels = (0..(width * height))
+            .map(|i| get_bit_msb(&bitmaps[..], offset + i))
+            .collect();
+        glyphs.push(BitmapGlyph {
+            codepoint: first + i as u32,
+            left: g[4] as i16,
+            top: g[5] as i16,
+            width,
+            height,
+            advance: g[3] as i16,
+            pixels,
+        });
+    }
+
+    Some(BitmapFont {
+        glyphs,
+        line_height: Some(y_advance),
+        ..Default::default()
+    })
+}
+
+pub fn parse_adafruit_gfx(source: &str) -> Result<BitmapFont> {
+    parse_adafruit_gfx_internal(source).ok_or_else(|| Error::new("Invalid Adafruit GFX font."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test glyphs as (character, left, top, rows). 'g' descends 2 pixels
+    // below the baseline.
+    const GLYPHS: [(char, i16, i16, &[&str]); 3] = [
+        (
+            'H',
+            0,
+            -7,
+            &[
+                "#...#", "#...#", "#####", "#...#", "#...#", "#...#", "#...#",
+            ],
+        ),
+        (
+            'g',
+            0,
+            -5,
+            &[
+                ".####", "#...#", "#...#", ".####", "....#", "#...#", ".###.",
+            ],
+        ),
+        ('\u{416}', 1, -3, &["#.#", ".#.", "#.#"]),
+    ];
+
+    const ADVANCE: i16 = 6;
+
+    fn get_pixels(rows: &[&str]) -> Vec<bool> {
+        rows.iter()
+            .flat_map(|r| r.chars().map(|c| c == '#'))
+            .collect()
+    }
+
+    // Packs rows of |pixels| into bytes, padding each row to |row_bytes|.
+    fn pack_rows(pixels: &[bool], width: usize, row_bytes: usize, msb: bool) -> Vec<u8> {
+        let mut result = Vec::new();
+        for row in pixels.chunks(width) {
+            let mut bytes = vec![0u8; row_bytes];
+            for (x, &p) in row.iter().enumerate() {
+                if p {
+                    bytes[x / 8] |= if msb { 0x80 >> (x % 8) } else { 1 << (x % 8) };
+                }
+            }
+            result.extend(bytes);
+        }
+        result
+    }
+
+    fn find_glyph(font: &BitmapFont, c: char) -> &BitmapGlyph {
+        font.glyphs
+            .iter()
+            .find(|g| g.codepoint == c as u32)
+            .expect("Missing glyph")
+    }
+
+    fn check_glyphs(font: &BitmapFont) {
+        assert_eq!(font.glyphs.len(), GLYPHS.len());
+        for &(c, left, top, rows) in GLYPHS.iter() {
+            let glyph = find_glyph(font, c);
+            assert_eq!((glyph.left, glyph.top), (left, top));
+            assert_eq!((glyph.width, glyph.height), (rows[0].len(), rows.len()));
+            assert_eq!(glyph.advance, ADVANCE);
+            assert_eq!(glyph.pixels, get_pixels(rows));
+        }
+    }
+
+    fn build_pcf(format: u32, pad: u32, compressed: bool) -> Vec<u8> {
+        let msb = format & PCF_BYTE_ORDER_MSB != 0;
+        let u16_bytes = |v: u16| {
+            if msb {
+                v.to_be_bytes().to_vec()
+            } else {
+                v.to_le_bytes().to_vec()
+            }
+        };
+        let u32_bytes = |v: u32| {
+            if msb {
+                v.to_be_bytes().to_vec()
+            } else {
+                v.to_le_bytes().to_vec()
+            }
+        };
+
+        let props = [("FONT_ASCENT", 8), ("FONT_DESCENT", 2), ("PIXEL_SIZE", 10)];
+        let mut properties = format.to_le_bytes().to_vec();
+        properties.extend(u32_bytes(props.len() as u32));
+        let mut strings = Vec::new();
+        for &(name, value) in props.iter() {
+            properties.extend(u32_bytes(strings.len() as u32));
+            properties.push(0);
+            properties.extend(u32_bytes(value));
+            strings.extend(name.bytes());
+            strings.push(0);
+        }
+        properties.resize(properties.len() + (4 - props.len() % 4) % 4, 0);
+        properties.extend(u32_bytes(strings.len() as u32));
+        properties.extend(strings);
+
+        let mut metrics;
+        if compressed {
+            metrics = (format | PCF_COMPRESSED_METRICS).to_le_bytes().to_vec();
+            metrics.extend(u16_bytes(GLYPHS.len() as u16));
+        } else {
+            metrics = format.to_le_bytes().to_vec();
+            metrics.extend(u32_bytes(GLYPHS.len() as u32));
+        }
+        for &(_, left, top, rows) in GLYPHS.iter() {
+            let right = left + rows[0].len() as i16;
+            let descent = top + rows.len() as i16;
+            for &v in [left, right, ADVANCE, -top, descent].iter() {
+                if compressed {
+                    metrics.push((v + 0x80) as u8);
+                } else {
+                    metrics.extend(u16_bytes(v as u16));
+                }
+            }
+            if !compressed {
+                // Attributes.
+                metrics.extend(u16_bytes(0));
+            }
+        }
+
+        let mut bitmaps = (format | pad).to_le_bytes().to_vec();
+        bitmaps.extend(u32_bytes(GLYPHS.len() as u32));
+        let mut data = Vec::new();
+        for &(_, _, _, rows) in GLYPHS.iter() {
+            bitmaps.extend(u32_bytes(data.len() as u32));
+            let row_bytes = (rows[0].len() + 7) / 8 * (1 << pad);
+            let bit_msb = format & PCF_BIT_ORDER_MSB != 0;
+            data.extend(pack_rows(
+                &get_pixels(rows),
+                rows[0].len(),
+                row_bytes,
+                bit_msb,
+            ));
+        }
+        for _ in 0..4 {
+            bitmaps.extend(u32_bytes(data.len() as u32));
+        }
+        bitmaps.extend(data);
+
+        // Encodings for byte1 0-4 and byte2 0-255, i.e. up to U+04FF.
+        let mut encodings = format.to_le_bytes().to_vec();
+        for &v in [0, 255, 0, 4, 'g' as u16].iter() {
+            encodings.extend(u16_bytes(v));
+        }
+        let mut indices = vec![0xffff; 5 * 256];
+        for (i, &(c, _, _, _)) in GLYPHS.iter().enumerate() {
+            indices[c as usize] = i as u16;
+        }
+        for index in indices {
+            encodings.extend(u16_bytes(index));
+        }
+
+        let tables = [
+            (PCF_PROPERTIES, properties),
+            (PCF_METRICS, metrics),
+            (PCF_BITMAPS, bitmaps),
+            (PCF_BDF_ENCODINGS, encodings),
+        ];
+        let mut result = b"\x01fcp".to_vec();
+        result.extend(&(tables.len() as u32).to_le_bytes());
+        let mut body = Vec::new();
+        for &(type_, ref table) in tables.iter() {
+            let offset = 8 + tables.len() * 16 + body.len();
+            result.extend(&type_.to_le_bytes());
+            result.extend(&table[..4]);
+            result.extend(&(table.len() as u32).to_le_bytes());
+            result.extend(&(offset as u32).to_le_bytes());
+            body.extend(table);
+            body.resize((body.len() + 3) / 4 * 4, 0);
+        }
+        result.extend(body);
+        result
+    }
+
+    #[test]
+    fn pcf() {
+        let formats = [
+            (PCF_BYTE_ORDER_MSB | PCF_BIT_ORDER_MSB, 2, false),
+            (0, 0, true),
+            (PCF_BIT_ORDER_MSB, 1, true),
+        ];
+        for &(format, pad, compressed) in formats.iter() {
+            let font = parse_pcf(&build_pcf(format, pad, compressed)).unwrap();
+            check_glyphs(&font);
+            assert_eq!(font.ascent, Some(8));
+            assert_eq!(font.descent, Some(2));
+            assert_eq!(font.pixel_size, Some(10));
+            assert_eq!(font.default_char, Some('g' as u32));
+        }
+        assert!(parse_pcf(b"\x1f\x8b\x08\x00").is_err());
+    }
+
+    // Returns pixels of a 8x10 PSF glyph with the baseline below row 7.
+    fn get_psf_cell(left: i16, top: i16, rows: &[&str]) -> Vec<bool> {
+        let mut cell = vec![false; 8 * 10];
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                let cx = x + left as usize;
+                let cy = y + (8 + top) as usize;
+                cell[cy * 8 + cx] = c == '#';
+            }
+        }
+        cell
+    }
+
+    fn check_psf_glyph(font: &BitmapFont, c: char) {
+        let &(_, left, top, rows) = GLYPHS.iter().find(|g| g.0 == c).unwrap();
+        let glyph = find_glyph(font, c);
+        assert_eq!((glyph.left, glyph.top), (0, -8));
+        assert_eq!((glyph.width, glyph.height, glyph.advance), (8, 10, 8));
+        assert_eq!(glyph.pixels, get_psf_cell(left, top, rows));
+    }
+
+    fn check_psf_metrics(font: &BitmapFont) {
+        assert_eq!(font.ascent, Some(8));
+        assert_eq!(font.descent, Some(2));
+        assert_eq!(font.line_height, Some(10));
+        assert_eq!(font.pixel_size, Some(10));
+    }
+
+    #[test]
+    fn psf1() {
+        let mut data = vec![0x36, 0x04, 0, 10];
+        for i in 0..256 {
+            let cell = match GLYPHS.iter().find(|g| g.0 as usize == i) {
+                Some(&(_, left, top, rows)) => get_psf_cell(left, top, rows),
+                None => vec![false; 8 * 10],
+            };
+            data.extend(pack_rows(&cell, 8, 1, true));
+        }
+
+        let font = parse_psf(&data).unwrap();
+        assert_eq!(font.glyphs.len(), 256);
+        check_psf_glyph(&font, 'H');
+        check_psf_glyph(&font, 'g');
+        check_psf_metrics(&font);
+    }
+
+    #[test]
+    fn psf2() {
+        let mut data = vec![0x72, 0xb5, 0x4a, 0x86];
+        for &v in [0, 32, PSF2_HAS_UNICODE_TABLE, 3, 10, 10, 8].iter() {
+            data.extend(&v.to_le_bytes());
+        }
+        for &(_, left, top, rows) in GLYPHS.iter() {
+            data.extend(pack_rows(&get_psf_cell(left, top, rows), 8, 1, true));
+        }
+        // 'H' is also used for Cyrillic EN. Sequences after 0xFE are
+        // skipped.
+        data.extend("H\u{41d}".bytes());
+        data.push(0xfe);
+        data.extend("H\u{301}".bytes());
+        data.push(0xff);
+        data.extend("g".bytes());
+        data.push(0xff);
+        data.extend("\u{416}".bytes());
+        data.push(0xff);
+
+        let font = parse_psf(&data).unwrap();
+        assert_eq!(font.glyphs.len(), 4);
+        for &c in ['H', 'g', '\u{416}'].iter() {
+            check_psf_glyph(&font, c);
+        }
+        assert_eq!(
+            find_glyph(&font, '\u{41d}').pixels,
+            find_glyph(&font, 'H').pixels
+        );
+        check_psf_metrics(&font);
+    }
+
+    struct BitWriter {
+        bits: Vec<bool>,
+    }
+
+    impl BitWriter {
+        fn write(&mut self, value: u32, bits: u8) {
+            for i in 0..bits {
+                self.bits.push(value & (1 << i) != 0);
+            }
+        }
+
+        fn to_bytes(&self) -> Vec<u8> {
+            pack_rows(
+                &self.bits,
+                self.bits.len(),
+                (self.bits.len() + 7) / 8,
+                false,
+            )
+        }
+    }
+
+    // Encodes glyph with 4 bits for size and position, 5 bits for advance
+    // and 3 bits for run lengths.
+    fn encode_u8g2_glyph(left: i16, top: i16, rows: &[&str]) -> Vec<u8> {
+        let (width, height) = (rows[0].len(), rows.len());
+        let mut writer = BitWriter { bits: Vec::new() };
+        writer.write(width as u32, 4);
+        writer.write(height as u32, 4);
+        writer.write((left + 8) as u32, 4);
+        writer.write((-top - height as i16 + 8) as u32, 4);
+        writer.write((ADVANCE + 16) as u32, 5);
+        let pixels = get_pixels(rows);
+        let mut i = 0;
+        while i < pixels.len() {
+            let mut zeros = 0;
+            while i < pixels.len() && !pixels[i] && zeros < 7 {
+                zeros += 1;
+                i += 1;
+            }
+            let mut ones = 0;
+            while i < pixels.len() && pixels[i] && ones < 7 {
+                ones += 1;
+                i += 1;
+            }
+            writer.write(zeros, 3);
+            writer.write(ones, 3);
+            writer.write(0, 1);
+        }
+        writer.to_bytes()
+    }
+
+    fn build_u8g2() -> Vec<u8> {
+        let mut ascii = Vec::new();
+        let mut unicode = vec![0, 4, 0xff, 0xff];
+        for &(c, left, top, rows) in GLYPHS.iter() {
+            let glyph = encode_u8g2_glyph(left, top, rows);
+            if (c as u32) < 0x100 {
+                ascii.extend(&[c as u8, glyph.len() as u8 + 2]);
+                ascii.extend(glyph);
+            } else {
+                unicode.extend(&(c as u16).to_be_bytes());
+                unicode.push(glyph.len() as u8 + 3);
+                unicode.extend(glyph);
+            }
+        }
+        ascii.push(0);
+        unicode.extend(&[0, 0]);
+
+        let mut data = vec![3, 0, 3, 3, 4, 4, 4, 4, 5, 5, 10, 0, 0xfe];
+        data.extend(&[7, 0xfe, 7, 0xfe, 0, 0, 0, 0]);
+        data.extend(&(ascii.len() as u16).to_be_bytes());
+        data.extend(ascii);
+        data.extend(unicode);
+        data
+    }
+
+    #[test]
+    fn u8g2() {
+        let font = parse_u8g2(&build_u8g2()).unwrap();
+        check_glyphs(&font);
+        assert_eq!(font.ascent, Some(7));
+        assert_eq!(font.descent, Some(2));
+        assert_eq!(font.pixel_size, Some(10));
+    }
+
+    #[test]
+    fn c_string() {
+        let data = build_u8g2();
+        let mut source = String::from("/* Fontname: test */\nconst uint8_t font[] = \n  \"");
+        for (i, b) in data.iter().enumerate() {
+            source.push_str(&format!("\\{:o}", b));
+            if i % 16 == 15 {
+                source.push_str("\"\n  \"");
+            }
+        }
+        source.push_str("\";\n");
+        assert_eq!(parse_c_string(&source).unwrap(), data);
+    }
+
+    #[test]
+    fn adafruit_gfx() {
+        let source = "
+            // Bitmap of 'g' { }
+            const uint8_t TestBitmaps[] PROGMEM = {
+              0x7C, 0x62, 0xF0, 0xC5, 0xC0 };
+
+            const GFXglyph TestGlyphs[] PROGMEM = {
+              {     0,   5,   7,   6,    0,   -5 },   // 0x67 'g'
+              {     5,   0,   0,   6,    0,    0 } }; // 0x68 'h'
+
+            const GFXfont Test PROGMEM = {
+              (uint8_t  *)TestBitmaps,
+              (GFXglyph *)TestGlyphs,
+              0x67, 0x68, 12 };
+        ";
+        let font = parse_adafruit_gfx(source).unwrap();
+        assert_eq!(font.glyphs.len(), 2);
+        let g = find_glyph(&font, 'g');
+        assert_eq!((g.left, g.top, g.width, g.height), (0, -5, 5, 7));
+        assert_eq!(g.advance, ADVANCE);
+        assert_eq!(g.pixels, get_pixels(GLYPHS[1].3));
+        assert_eq!(find_glyph(&font, 'h').pixels.len(), 0);
+        assert_eq!(font.line_height, Some(12));
+    }
+}
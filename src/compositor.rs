@@ -0,0 +1,348 @@
+use super::*;
+use gfx::{BlitOp, Canvas, Frame, Rect, Size, Vector};
+
+// How a layer is combined with the layers below it.
+pub enum Transparency {
+    // The layer covers everything below it.
+    Opaque,
+
+    // Only pixels that are set in the layer are drawn, the layers below show
+    // through the rest.
+    Transparent,
+
+    // Pixels that are set in the mask are copied from the layer, the rest
+    // are transparent. The mask must have the same size as the layer. Layer
+    // pixels outside of the mask are cleared when the layer is drawn.
+    Mask(Frame),
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct LayerId(usize);
+
+struct Layer {
+    id: LayerId,
+
+    // With Transparency::Mask the frame is kept masked, so it doesn't need
+    // to be copied on each compose() call.
+    frame: Frame,
+    position: Vector,
+    z: i32,
+    visible: bool,
+    transparency: Transparency,
+}
+
+impl Layer {
+    // Clears pixels that are outside of the mask.
+    fn apply_mask(&mut self) {
+        if let Transparency::Mask(ref mask) = self.transparency {
+            let rect = Rect::ps(Vector::zero(), mask.size());
+            self.frame.blit(mask, rect, Vector::zero(), BlitOp::And);
+        }
+    }
+}
+
+// Composes independently drawn layers, e.g. a status bar, a main view and
+// popups, into frames for a DisplayDriver. Each layer keeps its content, so
+// only layers that change need to be drawn again with draw_layer(). Changes
+// are tracked for the compositor as a whole: after any change show()
+// composes all layers from scratch, otherwise it doesn't update the display.
+pub struct Compositor {
+    size: Size,
+
+    // Sorted by z. Layers with the same z are kept in the order they were
+    // added.
+    layers: Vec<Layer>,
+
+    next_id: usize,
+    dirty: bool,
+}
+
+impl Compositor {
+    pub fn new(size: Size) -> Compositor {
+        Compositor {
+            size,
+            layers: Vec::new(),
+            next_id: 0,
+            dirty: true,
+        }
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    // Adds an empty opaque layer covering |rect|. Layers with higher |z|
+    // are drawn on top.
+    pub fn add_layer(&mut self, rect: Rect, z: i32) -> LayerId {
+        let id = LayerId(self.next_id);
+        self.next_id += 1;
+        self.insert_layer(Layer {
+            id,
+            frame: Frame::new(rect.size),
+            position: rect.pos,
+            z,
+            visible: true,
+            transparency: Transparency::Opaque,
+        });
+        id
+    }
+
+    pub fn remove_layer(&mut self, id: LayerId) {
+        let index = self.get_index(id);
+        self.layers.remove(index);
+        self.dirty = true;
+    }
+
+    fn insert_layer(&mut self, layer: Layer) {
+        let index = self
+            .layers
+            .iter()
+            .position(|l| l.z > layer.z)
+            .unwrap_or(self.layers.len());
+        self.layers.insert(index, layer);
+        self.dirty = true;
+    }
+
+    fn get_index(&self, id: LayerId) -> usize {
+        self.layers
+            .iter()
+            .position(|l| l.id == id)
+            .expect("Invalid layer id")
+    }
+
+    fn get_layer(&mut self, id: LayerId) -> &mut Layer {
+        let index = self.get_index(id);
+        &mut self.layers[index]
+    }
+
+    // Clears the layer and calls |draw| to draw its content. The canvas
+    // uses layer coordinates, i.e. (0, 0) is the top-left corner of the
+    // layer.
+    pub fn draw_layer<F>(&mut self, id: LayerId, draw: F)
+    where
+        F: FnOnce(&mut Canvas),
+    {
+        let layer = self.get_layer(id);
+        let mut frame = Frame::new(Size::zero());
+        std::mem::swap(&mut frame, &mut layer.frame);
+        frame.clear();
+        let mut canvas = Canvas::new(frame);
+        draw(&mut canvas);
+        layer.frame = canvas.take_frame();
+        layer.apply_mask();
+        self.dirty = true;
+    }
+
+    pub fn set_layer_position(&mut self, id: LayerId, position: Vector) {
+        let layer = self.get_layer(id);
+        if layer.position != position {
+            layer.position = position;
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_layer_visible(&mut self, id: LayerId, visible: bool) {
+        let layer = self.get_layer(id);
+        if layer.visible != visible {
+            layer.visible = visible;
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_layer_z(&mut self, id: LayerId, z: i32) {
+        let index = self.get_index(id);
+        if self.layers[index].z != z {
+            let mut layer = self.layers.remove(index);
+            layer.z = z;
+            self.insert_layer(layer);
+        }
+    }
+
+    pub fn set_layer_transparency(&mut self, id: LayerId, transparency: Transparency) {
+        let layer = self.get_layer(id);
+        if let Transparency::Mask(ref mask) = transparency {
+            assert!(mask.size() == layer.frame.size());
+        }
+        layer.transparency = transparency;
+        layer.apply_mask();
+        self.dirty = true;
+    }
+
+    // Returns true if layers have changed since the last compose() call.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    // Draws all visible layers to |frame|, bottom to top. Pixels that are
+    // not covered by any layer are cleared.
+    pub fn compose(&mut self, frame: &mut Frame) {
+        assert!(frame.size() == self.size);
+        frame.clear();
+        for layer in self.layers.iter().filter(|l| l.visible) {
+            let rect = Rect::ps(Vector::zero(), layer.frame.size());
+            match layer.transparency {
+                Transparency::Opaque => {
+                    frame.blit(&layer.frame, rect, layer.position, BlitOp::Copy)
+                }
+                Transparency::Transparent => {
+                    frame.blit(&layer.frame, rect, layer.position, BlitOp::Or)
+                }
+                Transparency::Mask(ref mask) => {
+                    frame.blit(mask, rect, layer.position, BlitOp::AndNot);
+                    frame.blit(&layer.frame, rect, layer.position, BlitOp::Or);
+                }
+            }
+        }
+        self.dirty = false;
+    }
+
+    // Composes layers and shows the result on |driver| if any of the layers
+    // have changed since the last call. All layers are composed again, not
+    // only the changed ones.
+    pub fn show<D: DisplayDriver>(&mut self, driver: &mut D) {
+        if !self.dirty {
+            return;
+        }
+        let mut frame = driver.get_frame();
+        self.compose(&mut frame);
+        driver.show_frame(frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gfx::Color;
+
+    const SIZE: Size = Size {
+        width: 8,
+        height: 8,
+    };
+
+    fn full_rect() -> Rect {
+        Rect::ps(Vector::zero(), SIZE)
+    }
+
+    fn compose(compositor: &mut Compositor) -> Frame {
+        let mut frame = Frame::new(SIZE);
+        compositor.compose(&mut frame);
+        frame
+    }
+
+    fn fill(compositor: &mut Compositor, id: LayerId, rect: Rect) {
+        compositor.draw_layer(id, |c| c.draw_rect(rect, Color::Light));
+    }
+
+    #[test]
+    fn opaque_layer_hides_layers_below() {
+        let mut compositor = Compositor::new(SIZE);
+        let bottom = compositor.add_layer(full_rect(), 0);
+        fill(&mut compositor, bottom, full_rect());
+        compositor.add_layer(Rect::xywh(2, 2, 4, 4), 1);
+
+        let frame = compose(&mut compositor);
+        assert!(frame.get_pixel(1, 1));
+        assert!(!frame.get_pixel(2, 2));
+        assert!(!frame.get_pixel(5, 5));
+        assert!(frame.get_pixel(6, 6));
+    }
+
+    #[test]
+    fn transparent_layer_is_ored() {
+        let mut compositor = Compositor::new(SIZE);
+        let bottom = compositor.add_layer(full_rect(), 0);
+        fill(&mut compositor, bottom, Rect::xywh(0, 0, 8, 1));
+        let top = compositor.add_layer(full_rect(), 1);
+        fill(&mut compositor, top, Rect::xywh(0, 0, 1, 8));
+        compositor.set_layer_transparency(top, Transparency::Transparent);
+
+        let frame = compose(&mut compositor);
+        assert!(frame.get_pixel(7, 0));
+        assert!(frame.get_pixel(0, 7));
+        assert!(!frame.get_pixel(1, 1));
+    }
+
+    #[test]
+    fn masked_layer_keeps_layers_below() {
+        let mut compositor = Compositor::new(SIZE);
+        let bottom = compositor.add_layer(full_rect(), 0);
+        fill(&mut compositor, bottom, full_rect());
+
+        // The top layer is empty, pixels in the mask clear the layer below.
+        let top = compositor.add_layer(full_rect(), 1);
+        let mut mask = Canvas::new(Frame::new(SIZE));
+        mask.draw_rect(Rect::xywh(0, 0, 4, 8), Color::Light);
+        compositor.set_layer_transparency(top, Transparency::Mask(mask.take_frame()));
+
+        let frame = compose(&mut compositor);
+        assert!(!frame.get_pixel(3, 3));
+        assert!(frame.get_pixel(4, 3));
+
+        // Content outside of the mask is not drawn.
+        fill(&mut compositor, top, Rect::xywh(2, 0, 4, 8));
+        let frame = compose(&mut compositor);
+        assert!(!frame.get_pixel(1, 3));
+        assert!(frame.get_pixel(2, 3));
+        assert!(frame.get_pixel(4, 3));
+        assert!(frame.get_pixel(7, 3));
+    }
+
+    #[test]
+    fn layer_partly_off_screen() {
+        let mut compositor = Compositor::new(SIZE);
+        let layer = compositor.add_layer(Rect::xywh(-2, -3, 4, 4), 0);
+        fill(&mut compositor, layer, Rect::xywh(0, 0, 4, 4));
+
+        let frame = compose(&mut compositor);
+        assert!(frame.get_pixel(0, 0));
+        assert!(frame.get_pixel(1, 0));
+        assert!(!frame.get_pixel(2, 0));
+        assert!(!frame.get_pixel(0, 1));
+
+        compositor.set_layer_position(layer, Vector::xy(6, 7));
+        let frame = compose(&mut compositor);
+        assert!(!frame.get_pixel(0, 0));
+        assert!(frame.get_pixel(7, 7));
+        assert!(!frame.get_pixel(5, 7));
+    }
+
+    #[test]
+    fn dirty_flag() {
+        let mut compositor = Compositor::new(SIZE);
+        assert!(compositor.is_dirty());
+        let layer = compositor.add_layer(full_rect(), 0);
+        compose(&mut compositor);
+        assert!(!compositor.is_dirty());
+
+        compositor.set_layer_visible(layer, true);
+        assert!(!compositor.is_dirty());
+        compositor.set_layer_visible(layer, false);
+        assert!(compositor.is_dirty());
+        compose(&mut compositor);
+        assert!(!compositor.is_dirty());
+
+        fill(&mut compositor, layer, full_rect());
+        assert!(compositor.is_dirty());
+    }
+
+    #[test]
+    fn z_order() {
+        let mut compositor = Compositor::new(SIZE);
+        let a = compositor.add_layer(full_rect(), 0);
+        let b = compositor.add_layer(full_rect(), 0);
+        fill(&mut compositor, a, full_rect());
+
+        // Layers with the same z are drawn in the order they were added.
+        assert!(!compose(&mut compositor).get_pixel(0, 0));
+
+        compositor.set_layer_z(a, 1);
+        assert!(compositor.is_dirty());
+        assert!(compose(&mut compositor).get_pixel(0, 0));
+
+        compositor.set_layer_z(b, 1);
+        assert!(!compose(&mut compositor).get_pixel(0, 0));
+
+        compositor.set_layer_z(b, -1);
+        assert!(compose(&mut compositor).get_pixel(0, 0));
+    }
+}
@@ -5,3 +5,20 @@ pub trait DisplayDriver {
     fn show_frame(&mut self, frame: gfx::Frame);
     fn get_frame(&mut self) -> gfx::Frame;
 }
+
+// Helper for show_frame_diff() in page-addressed drivers, used to find
+// spans that a different between |a| and |b|. Two spans that are less than
+// 5 pixels apart are considered part of the same span to account for the
+// costs of sending page address.
+pub fn find_span_end(a: &[u8], b: &[u8]) -> usize {
+    let mut last_diff = 0;
+    for i in 1..a.len() {
+        if a[i] != b[i] {
+            last_diff = i;
+        }
+        if i - last_diff > 4 {
+            return last_diff + 1;
+        }
+    }
+    last_diff + 1
+}
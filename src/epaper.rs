@@ -0,0 +1,386 @@
+extern crate rppal;
+
+use self::rppal::gpio;
+use super::*;
+use spi_bus::SpiBus;
+use std::thread;
+use std::time;
+
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub enum EPaperType {
+    // SSD1680, e.g. 2.13" 122x250 and 2.9" 128x296 panels.
+    Ssd1680,
+
+    // IL0373 (also sold as UC8151), e.g. 2.13" 104x212 flexible panels.
+    Il0373,
+}
+
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub enum RefreshPolicy {
+    // Every frame is shown with a full refresh. Slow and flickers, but never
+    // leaves any ghosting.
+    Full,
+
+    // Frames are shown with partial refresh. Every N-th update is a full
+    // refresh to clean up ghosting accumulated by partial updates.
+    PartialWithFullEvery(usize),
+}
+
+#[derive(Clone, Copy)]
+pub struct EPaperConfig {
+    pub type_: EPaperType,
+
+    // The reset pin is required to wake the panel from deep sleep.
+    pub spi: SpiConfig,
+    pub busy_pin: u8,
+
+    // Size of the panel in its native orientation. Width is the number of
+    // source lines and height is the number of gate lines.
+    pub size: gfx::Size,
+
+    pub refresh_policy: RefreshPolicy,
+
+    // By default Light pixels are shown black on white background. Set this
+    // flag to show them white on black.
+    pub invert: bool,
+}
+
+impl EPaperConfig {
+    pub fn new(type_: EPaperType, spi: SpiConfig, busy_pin: u8, size: gfx::Size) -> EPaperConfig {
+        EPaperConfig {
+            type_,
+            spi,
+            busy_pin,
+            size,
+            refresh_policy: RefreshPolicy::PartialWithFullEvery(20),
+            invert: false,
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Copy, Clone)]
+enum RefreshMode {
+    Full,
+    Partial,
+}
+
+pub struct EPaper {
+    config: EPaperConfig,
+    bus: SpiBus,
+    cur_frame: Option<gfx::Frame>,
+    old_frame: Option<gfx::Frame>,
+    partial_updates: usize,
+    force_full_refresh: bool,
+    asleep: bool,
+}
+
+const BUSY_TIMEOUT_MS: u64 = 10_000;
+
+// IL0373 waveforms for partial refresh. The OTP waveforms built into the
+// controller support only full refresh.
+const IL0373_LUT_VCOM_PARTIAL: [u8; 44] = [
+    0x00, 0x19, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+const IL0373_LUT_WW_PARTIAL: [u8; 42] = [
+    0x00, 0x19, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+const IL0373_LUT_BW_PARTIAL: [u8; 42] = [
+    0x80, 0x19, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+const IL0373_LUT_WB_PARTIAL: [u8; 42] = [
+    0x40, 0x19, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+const IL0373_LUT_BB_PARTIAL: [u8; 42] = [
+    0x00, 0x19, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+// Converts |frame| from the page-major layout used by gfx::Frame to the
+// row-major layout used by e-paper controllers: each row starts at a byte
+// boundary and the most significant bit is the leftmost pixel. Set bits are
+// white.
+fn frame_to_rows(frame: &gfx::Frame, invert: bool) -> Vec<u8> {
+    let size = frame.size();
+    let bytes_per_row = (size.width + 7) / 8;
+    let mut result = vec![0u8; bytes_per_row * size.height];
+    for y in 0..size.height {
+        let row = &mut result[y * bytes_per_row..(y + 1) * bytes_per_row];
+        for x in 0..size.width {
+            if frame.get_pixel(x, y) == invert {
+                row[x / 8] |= 0x80 >> (x % 8);
+            }
+        }
+    }
+    result
+}
+
+impl EPaper {
+    pub fn new(config: EPaperConfig) -> Result<EPaper> {
+        if config.spi.reset_pin.is_none() {
+            return Err(Error::new("E-paper displays require reset pin."));
+        }
+
+        let mut bus = SpiBus::new(&config.spi)?;
+        bus.gpio().set_mode(config.busy_pin, gpio::Mode::Input);
+        let mut result = EPaper {
+            config,
+            bus,
+            cur_frame: None,
+            old_frame: None,
+            partial_updates: 0,
+            force_full_refresh: true,
+            asleep: false,
+        };
+        result.initialize()?;
+
+        Ok(result)
+    }
+
+    pub fn set_refresh_policy(&mut self, policy: RefreshPolicy) {
+        self.config.refresh_policy = policy;
+    }
+
+    // Makes the next frame to be shown with full refresh, regardless of the
+    // refresh policy.
+    pub fn force_full_refresh(&mut self) {
+        self.force_full_refresh = true;
+    }
+
+    // Puts the controller to deep sleep. The panel keeps showing the last
+    // frame. The controller is woken up when the next frame is shown.
+    pub fn sleep(&mut self) -> Result<()> {
+        if self.asleep {
+            return Ok(());
+        }
+        match self.config.type_ {
+            EPaperType::Ssd1680 => {
+                self.command(0x10, &[0x01])?;
+            }
+            EPaperType::Il0373 => {
+                // Power off, then deep sleep.
+                self.command(0x02, &[])?;
+                self.wait_busy()?;
+                self.command(0x07, &[0xA5])?;
+            }
+        }
+        self.asleep = true;
+        Ok(())
+    }
+
+    fn is_busy(&mut self) -> Result<bool> {
+        let level = self.bus.gpio().read(self.config.busy_pin)?;
+
+        // BUSY is active high on SSD1680 and active low on IL0373.
+        Ok(match self.config.type_ {
+            EPaperType::Ssd1680 => level == gpio::Level::High,
+            EPaperType::Il0373 => level == gpio::Level::Low,
+        })
+    }
+
+    fn wait_busy(&mut self) -> Result<()> {
+        let start = time::Instant::now();
+        while self.is_busy()? {
+            if start.elapsed() > time::Duration::from_millis(BUSY_TIMEOUT_MS) {
+                return Err(Error::new("Timed out waiting for e-paper controller."));
+            }
+            thread::sleep(time::Duration::from_millis(10));
+        }
+        Ok(())
+    }
+
+    fn command(&mut self, cmd: u8, data: &[u8]) -> Result<()> {
+        self.bus.send_command(&[cmd])?;
+        if !data.is_empty() {
+            self.bus.send_data(data)?;
+        }
+        Ok(())
+    }
+
+    fn initialize(&mut self) -> Result<()> {
+        self.bus.hardware_reset();
+        self.wait_busy()?;
+
+        let width = self.config.size.width;
+        let height = self.config.size.height;
+
+        match self.config.type_ {
+            EPaperType::Ssd1680 => {
+                // Software reset.
+                self.command(0x12, &[])?;
+                self.wait_busy()?;
+
+                // Driver output control: number of gate lines.
+                let last_gate = height - 1;
+                self.command(0x01, &[last_gate as u8, (last_gate >> 8) as u8, 0x00])?;
+
+                // Data entry mode: X and Y increment.
+                self.command(0x11, &[0x03])?;
+
+                // RAM X range, in bytes, and Y range, in lines.
+                self.command(0x44, &[0x00, ((width - 1) / 8) as u8])?;
+                self.command(0x45, &[0x00, 0x00, last_gate as u8, (last_gate >> 8) as u8])?;
+
+                // Border waveform.
+                self.command(0x3C, &[0x05])?;
+
+                // Use internal temperature sensor.
+                self.command(0x18, &[0x80])?;
+            }
+            EPaperType::Il0373 => {
+                // Power setting.
+                self.command(0x01, &[0x03, 0x00, 0x2B, 0x2B, 0x09])?;
+
+                // Booster soft start.
+                self.command(0x06, &[0x17, 0x17, 0x17])?;
+
+                // Power on.
+                self.command(0x04, &[])?;
+                self.wait_busy()?;
+
+                // PLL: 50Hz.
+                self.command(0x30, &[0x3A])?;
+
+                // Resolution.
+                self.command(0x61, &[width as u8, (height >> 8) as u8, height as u8])?;
+
+                // VCOM and data interval.
+                self.command(0x50, &[0x97])?;
+            }
+        }
+
+        self.asleep = false;
+
+        Ok(())
+    }
+
+    fn refresh_ssd1680(&mut self, new: &[u8], old: &[u8], mode: RefreshMode) -> Result<()> {
+        // Reset RAM address counters.
+        self.command(0x4E, &[0x00])?;
+        self.command(0x4F, &[0x00, 0x00])?;
+        self.command(0x24, new)?;
+
+        // The controller uses the second RAM bank as the previous image for
+        // partial refresh.
+        self.command(0x4E, &[0x00])?;
+        self.command(0x4F, &[0x00, 0x00])?;
+        self.command(0x26, old)?;
+
+        let update_sequence = match mode {
+            RefreshMode::Full => 0xF7,
+            RefreshMode::Partial => 0xFC,
+        };
+        self.command(0x22, &[update_sequence])?;
+        self.command(0x20, &[])?;
+        self.wait_busy()
+    }
+
+    fn refresh_il0373(&mut self, new: &[u8], old: &[u8], mode: RefreshMode) -> Result<()> {
+        match mode {
+            RefreshMode::Full => {
+                // Panel setting: use waveforms from OTP.
+                self.command(0x00, &[0x9F])?;
+                self.command(0x50, &[0x97])?;
+            }
+            RefreshMode::Partial => {
+                // Panel setting: use waveforms from registers.
+                self.command(0x00, &[0xBF])?;
+                self.command(0x50, &[0x17])?;
+                self.command(0x20, &IL0373_LUT_VCOM_PARTIAL)?;
+                self.command(0x21, &IL0373_LUT_WW_PARTIAL)?;
+                self.command(0x22, &IL0373_LUT_BW_PARTIAL)?;
+                self.command(0x23, &IL0373_LUT_WB_PARTIAL)?;
+                self.command(0x24, &IL0373_LUT_BB_PARTIAL)?;
+            }
+        }
+
+        self.command(0x10, old)?;
+        self.command(0x13, new)?;
+        self.command(0x12, &[])?;
+        self.wait_busy()
+    }
+
+    fn show_frame_internal(&mut self, frame: gfx::Frame) -> Result<()> {
+        assert!(frame.size() == self.config.size);
+
+        // Refresh is slow and visible, so it's skipped when nothing changed.
+        if let Some(ref cur) = self.cur_frame {
+            if cur.data() == frame.data() {
+                return Ok(());
+            }
+        }
+
+        if self.asleep {
+            self.initialize()?;
+        }
+
+        let mode = if self.force_full_refresh || self.cur_frame.is_none() {
+            RefreshMode::Full
+        } else {
+            match self.config.refresh_policy {
+                RefreshPolicy::Full => RefreshMode::Full,
+                RefreshPolicy::PartialWithFullEvery(n) if self.partial_updates >= n => {
+                    RefreshMode::Full
+                }
+                RefreshPolicy::PartialWithFullEvery(_) => RefreshMode::Partial,
+            }
+        };
+
+        let invert = self.config.invert;
+        let new = frame_to_rows(&frame, invert);
+        let old = match self.cur_frame {
+            Some(ref cur) => frame_to_rows(cur, invert),
+            None => new.clone(),
+        };
+
+        match self.config.type_ {
+            EPaperType::Ssd1680 => self.refresh_ssd1680(&new, &old, mode)?,
+            EPaperType::Il0373 => self.refresh_il0373(&new, &old, mode)?,
+        }
+
+        match mode {
+            RefreshMode::Full => {
+                self.partial_updates = 0;
+                self.force_full_refresh = false;
+            }
+            RefreshMode::Partial => self.partial_updates += 1,
+        }
+
+        if let Some(old) = self.cur_frame.take() {
+            self.old_frame = Some(old);
+        }
+        self.cur_frame = Some(frame);
+
+        Ok(())
+    }
+}
+
+impl DisplayDriver for EPaper {
+    fn size(&self) -> gfx::Size {
+        self.config.size
+    }
+
+    fn show_frame(&mut self, frame: gfx::Frame) {
+        if let Err(e) = self.show_frame_internal(frame) {
+            println!("EPaper: Failed to push a frame: {}", e.msg);
+        }
+    }
+
+    fn get_frame(&mut self) -> gfx::Frame {
+        match self.old_frame.take() {
+            Some(mut f) => {
+                f.clear();
+                f
+            }
+            None => gfx::Frame::new(self.size()),
+        }
+    }
+}
@@ -1,15 +1,27 @@
 extern crate bdf;
 extern crate font_rs;
+extern crate unicode_bidi;
+extern crate unicode_normalization;
 
+use self::unicode_bidi::BidiInfo;
+use self::unicode_normalization::char::{
+    canonical_combining_class, decompose_canonical, is_combining_mark,
+};
+use self::unicode_normalization::UnicodeNormalization;
 use std;
+use std::borrow::Cow;
 use std::cmp;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io::Read;
-use std::ops::Add;
+use std::ops::{Add, Deref};
+use std::ptr;
+use std::sync::{Arc, Mutex};
 
 use super::Error;
 use super::Result;
+use bitmap_font;
+use ttf;
 
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Error {
@@ -132,6 +144,28 @@ impl Rect {
     }
 }
 
+// Clockwise rotation.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Rotation {
+    Rotate0,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+// Operation used by Frame::blit() to combine source and destination pixels.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum BlitOp {
+    // Destination pixels are replaced with source pixels.
+    Copy,
+    Or,
+    And,
+    Xor,
+
+    // Pixels set in the source are cleared in the destination.
+    AndNot,
+}
+
 #[derive(Clone)]
 pub struct Frame {
     size: Size,
@@ -140,7 +174,9 @@ pub struct Frame {
 
 impl Frame {
     pub fn new(size: Size) -> Frame {
-        let buf_size = size.width * size.height / 8;
+        // The last row is partially filled when the height is not a
+        // multiple of 8.
+        let buf_size = size.width * ((size.height + 7) / 8);
         Frame {
             size,
             data: vec![0; buf_size],
@@ -158,7 +194,11 @@ impl Frame {
     }
 
     pub fn num_rows(&self) -> usize {
-        self.size.height / 8
+        (self.size.height + 7) / 8
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> bool {
+        (self.data[(y / 8) * self.size.width + x] >> (y % 8)) & 1 > 0
     }
 
     pub fn mut_data(&mut self) -> &mut [u8] {
@@ -168,23 +208,484 @@ impl Frame {
     pub fn data(&self) -> &[u8] {
         &self.data[..]
     }
+
+    // Combines |src_rect| of |src| with the rect of the same size at
+    // |dst_pos| in this frame. Parts of the rects that are outside of the
+    // frames are ignored.
+    pub fn blit(&mut self, src: &Frame, src_rect: Rect, dst_pos: Vector, op: BlitOp) {
+        // Clip the source rect to both frames.
+        let src_clipped = src_rect.intersect(Rect::ps(Vector::zero(), src.size));
+        let offset = Vector::xy(dst_pos.x - src_rect.left(), dst_pos.y - src_rect.top());
+        let dst_rect = src_clipped
+            .translate(offset)
+            .intersect(Rect::ps(Vector::zero(), self.size));
+        if dst_rect.size.width == 0 || dst_rect.size.height == 0 {
+            return;
+        }
+        let src_left = (dst_rect.left() - offset.x) as usize;
+        let src_top = (dst_rect.top() - offset.y) as usize;
+        let dst_left = dst_rect.left() as usize;
+        let dst_top = dst_rect.top() as usize;
+        let width = dst_rect.size.width;
+        let height = dst_rect.size.height;
+
+        // Pixels are copied in chunks of up to 8 rows that don't cross page
+        // boundaries in either frame.
+        let mut y = 0;
+        while y < height {
+            let in_y = src_top + y;
+            let out_y = dst_top + y;
+            let rows = cmp::min(cmp::min(8 - in_y % 8, 8 - out_y % 8), height - y);
+            let mask = (0xffu8 >> (8 - rows)) << (out_y % 8);
+            let in_row = (in_y / 8) * src.size.width + src_left;
+            let out_row = (out_y / 8) * self.size.width + dst_left;
+            for x in 0..width {
+                let inp = ((src.data[in_row + x] >> (in_y % 8)) << (out_y % 8)) & mask;
+                let out = &mut self.data[out_row + x];
+                *out = match op {
+                    BlitOp::Copy => (*out & !mask) | inp,
+                    BlitOp::Or => *out | inp,
+                    BlitOp::And => *out & (inp | !mask),
+                    BlitOp::Xor => *out ^ inp,
+                    BlitOp::AndNot => *out & !inp,
+                };
+            }
+            y += rows;
+        }
+    }
+
+    // Returns the frame with rows and columns swapped, i.e. mirrored along
+    // the main diagonal. Pixels are transposed in 8x8 blocks: each block is
+    // 8 consecutive bytes of a row of pages.
+    pub fn transpose(&self) -> Frame {
+        let width = self.size.width;
+        let height = self.size.height;
+        let mut result = Frame::new(Size::wh(height, width));
+        for row in 0..self.num_rows() {
+            for block_x in (0..width).step_by(8) {
+                let mut block = 0u64;
+                for i in 0..cmp::min(8, width - block_x) {
+                    block |= (self.data[row * width + block_x + i] as u64) << (i * 8);
+                }
+                let block = transpose_block(block);
+
+                // Columns of the block become a row of bytes in page
+                // |block_x / 8| of the result.
+                let out_row = block_x / 8;
+                for b in 0..cmp::min(8, height - row * 8) {
+                    result.data[out_row * height + row * 8 + b] = (block >> (b * 8)) as u8;
+                }
+            }
+        }
+        result
+    }
+
+    // Returns the frame mirrored left to right.
+    pub fn flip_horizontal(&self) -> Frame {
+        let mut result = self.clone();
+        if self.size.width == 0 {
+            return result;
+        }
+        for row in result.data.chunks_mut(self.size.width) {
+            row.reverse();
+        }
+        result
+    }
+
+    // Returns the frame mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Frame {
+        let width = self.size.width;
+        let rows = self.num_rows();
+        let mut result = Frame::new(self.size);
+        if width == 0 || rows == 0 {
+            return result;
+        }
+
+        // Reversing order of pages and bits in each byte mirrors the padded
+        // frame, so the result needs to be shifted up by the padding.
+        let pad = rows * 8 - self.size.height;
+        for x in 0..width {
+            let get = |row: usize| -> u8 {
+                if row < rows {
+                    self.data[(rows - 1 - row) * width + x].reverse_bits()
+                } else {
+                    0
+                }
+            };
+            for row in 0..rows {
+                let value = if pad == 0 {
+                    get(row)
+                } else {
+                    (get(row) >> pad) | (get(row + 1) << (8 - pad))
+                };
+                result.data[row * width + x] = value;
+            }
+        }
+
+        // Clear bits of the last page that are below the frame.
+        if pad > 0 {
+            let mask = 0xffu8 >> pad;
+            for byte in result.data[(rows - 1) * width..].iter_mut() {
+                *byte &= mask;
+            }
+        }
+        result
+    }
+
+    // Returns the frame rotated clockwise. Frames rotated by 90 or 270
+    // degrees have width and height swapped.
+    pub fn rotate(&self, rotation: Rotation) -> Frame {
+        match rotation {
+            Rotation::Rotate0 => self.clone(),
+            Rotation::Rotate90 => self.transpose().flip_horizontal(),
+            Rotation::Rotate180 => self.flip_horizontal().flip_vertical(),
+            Rotation::Rotate270 => self.transpose().flip_vertical(),
+        }
+    }
+}
+
+// Transposes 8x8 bit matrix stored with bit |c| of byte |r| representing
+// element (r, c).
+fn transpose_block(mut x: u64) -> u64 {
+    let t = (x ^ (x >> 7)) & 0x00aa_00aa_00aa_00aa;
+    x ^= t ^ (t << 7);
+    let t = (x ^ (x >> 14)) & 0x0000_cccc_0000_cccc;
+    x ^= t ^ (t << 14);
+    let t = (x ^ (x >> 28)) & 0x0000_0000_f0f0_f0f0;
+    x ^= t ^ (t << 28);
+    x
 }
 
 struct Glyph {
     rect: Rect,
-    width: usize,
+
+    // Horizontal distance the pen moves after the glyph. Fractional for
+    // TTF fonts.
+    advance: f32,
+
+    // Glyph ID in TTF fonts, used for kerning.
+    id: u16,
+
+    // Glyphs of static fonts borrow their bitmaps from the static tables.
+    data: Cow<'static, [u8]>,
+}
+
+// Glyph returned by font lookups. Glyphs of static fonts are not stored
+// anywhere, they are created for each lookup.
+enum GlyphRef {
+    Shared(Arc<Glyph>),
+    Static(Glyph),
+}
+
+impl Deref for GlyphRef {
+    type Target = Glyph;
+
+    fn deref(&self) -> &Glyph {
+        match *self {
+            GlyphRef::Shared(ref glyph) => glyph,
+            GlyphRef::Static(ref glyph) => glyph,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct FontMetrics {
+    // Distance from the baseline to the top of the tallest glyphs.
+    pub ascent: i16,
+
+    // Distance from the baseline to the bottom of the lowest glyphs.
+    pub descent: i16,
+
+    // Distance between baselines of consecutive lines.
+    pub line_height: i16,
+
+    // Nominal size of the font.
+    pub pixel_size: i16,
+}
+
+// Glyph that is drawn in place of characters that are missing in the font.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Replacement {
+    // Nothing is drawn, the pen is moved by a fixed distance.
+    Blank,
+
+    // Hollow box of the size of a capital letter.
+    Box,
+
+    // The specified character, e.g. '?'. Falls back to Box when the font
+    // doesn't have it.
+    Char(char),
+}
+
+// Default number of glyphs kept in the cache of a TTF font.
+const DEFAULT_GLYPH_CACHE_SIZE: usize = 256;
+
+// Cache of rasterised glyphs. When it's full the least recently used glyph
+// is evicted. Missing glyphs are cached as None.
+struct GlyphCache {
+    capacity: usize,
+    glyphs: HashMap<u32, (Option<Arc<Glyph>>, u64)>,
+    clock: u64,
+}
+
+impl GlyphCache {
+    fn new(capacity: usize) -> GlyphCache {
+        GlyphCache {
+            capacity,
+            glyphs: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    fn get(&mut self, codepoint: u32) -> Option<Option<Arc<Glyph>>> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.glyphs.get_mut(&codepoint).map(|entry| {
+            entry.1 = clock;
+            entry.0.clone()
+        })
+    }
+
+    fn insert(&mut self, codepoint: u32, glyph: Option<Arc<Glyph>>) {
+        while !self.glyphs.is_empty() && self.glyphs.len() >= self.capacity {
+            let oldest = *self.glyphs.iter().min_by_key(|e| (e.1).1).unwrap().0;
+            self.glyphs.remove(&oldest);
+        }
+        if self.capacity > 0 {
+            self.glyphs.insert(codepoint, (glyph, self.clock));
+        }
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.glyphs.len() > capacity {
+            let oldest = *self.glyphs.iter().min_by_key(|e| (e.1).1).unwrap().0;
+            self.glyphs.remove(&oldest);
+        }
+    }
+}
+
+// TTF font that is rasterised on demand.
+struct OutlineFont {
+    // font_rs::font::Font borrows the font data, so the data is kept here
+    // and parsed again for each glyph. Parsing only reads the table
+    // directory, so it's cheap compared to rasterisation.
     data: Vec<u8>,
+    size: usize,
+    options: TtfRenderOptions,
+
+    // Pixels per font unit.
+    scale: f32,
+
+    kerning: ttf::KerningTables,
+    cache: Mutex<GlyphCache>,
+}
+
+impl OutlineFont {
+    fn get_glyph(&self, codepoint: u32) -> Option<Arc<Glyph>> {
+        let mut cache = self.cache.lock().unwrap();
+        if let Some(glyph) = cache.get(codepoint) {
+            return glyph;
+        }
+        let glyph = self.render(codepoint).map(Arc::new);
+        cache.insert(codepoint, glyph.clone());
+        glyph
+    }
+
+    fn render(&self, codepoint: u32) -> Option<Glyph> {
+        let font = font_rs::font::parse(&self.data[..]).ok()?;
+        let sfnt = ttf::Sfnt::new(&self.data[..]);
+        let id = sfnt
+            .lookup_glyph_id(codepoint)
+            .or_else(|| font.lookup_glyph_id(codepoint))?;
+        // font_rs fails to render glyphs without outlines, e.g. space.
+        let mut glyph = if sfnt.is_empty_glyph(id) {
+            Glyph {
+                rect: Rect::zero(),
+                advance: 0.0,
+                id,
+                data: Cow::Borrowed(&[]),
+            }
+        } else {
+            render_glyph(&font, id, self.size, &self.options)?
+        };
+        if let Some(advance) = sfnt.get_advance_width(id) {
+            glyph.advance = advance as f32 * self.scale + self.options.embolden as f32;
+        }
+        Some(glyph)
+    }
+
+    fn get_kerning(&self, left: u16, right: u16) -> f32 {
+        let sfnt = ttf::Sfnt::new(&self.data[..]);
+        sfnt.get_kerning(&self.kerning, left, right) as f32 * self.scale
+    }
+}
+
+enum GlyphSource {
+    Bitmap(BTreeMap<u32, Arc<Glyph>>),
+    Outline(OutlineFont),
+    Static(&'static StaticFont),
 }
 
 pub struct Font {
-    glyphs: BTreeMap<u32, Glyph>,
+    glyphs: GlyphSource,
+    metrics: FontMetrics,
+    replacement: Replacement,
+    box_glyph: Arc<Glyph>,
+
+    // Fonts that are searched for glyphs that are missing in this font.
+    fallbacks: Vec<Font>,
+}
+
+// Horizontal advance for characters that are missing in the font.
+const MISSING_GLYPH_ADVANCE: i16 = 5;
+
+// Creates a hollow box glyph that fits between the baseline and the ascent.
+fn make_box_glyph(metrics: &FontMetrics) -> Glyph {
+    let height = cmp::max(metrics.ascent, 3) as usize;
+    let width = cmp::max(height / 2 + 1, 3);
+    let mut data = vec![0u8; width * ((height + 7) / 8)];
+    for y in 0..height {
+        for x in 0..width {
+            if x == 0 || x == width - 1 || y == 0 || y == height - 1 {
+                data[x + (y / 8) * width] |= 1 << (y % 8);
+            }
+        }
+    }
+    Glyph {
+        rect: Rect::xywh(1, -(height as i16), width, height),
+        advance: (width + 2) as f32,
+        id: 0,
+        data: Cow::Owned(data),
+    }
+}
+
+// Returns metrics calculated from bounding boxes of the glyphs. Used for
+// fonts that don't specify their metrics.
+fn get_glyphs_metrics(glyphs: &BTreeMap<u32, Arc<Glyph>>) -> FontMetrics {
+    let mut ascent = 0;
+    let mut descent = 0;
+    for glyph in glyphs.values() {
+        ascent = cmp::max(ascent, -glyph.rect.top());
+        descent = cmp::max(descent, glyph.rect.bottom());
+    }
+    FontMetrics {
+        ascent,
+        descent,
+        line_height: ascent + descent,
+        pixel_size: ascent + descent,
+    }
+}
+
+fn get_bdf_property(font: &bdf::Font, name: &str) -> Option<i16> {
+    match font.properties().get(name) {
+        Some(&bdf::Property::Integer(v)) => Some(v as i16),
+        _ => None,
+    }
+}
+
+// Returns character property, e.g. DEFAULT_CHAR. These are code points, so
+// they don't fit in i16.
+fn get_bdf_char_property(font: &bdf::Font, name: &str) -> Option<char> {
+    match font.properties().get(name) {
+        Some(&bdf::Property::Integer(v)) if v >= 0 && v <= u32::MAX as i64 => {
+            std::char::from_u32(v as u32)
+        }
+        _ => None,
+    }
+}
+
+fn get_bdf_metrics(font: &bdf::Font, glyphs: &BTreeMap<u32, Arc<Glyph>>) -> FontMetrics {
+    let mut metrics = get_glyphs_metrics(glyphs);
+    if let Some(ascent) = get_bdf_property(font, "FONT_ASCENT") {
+        metrics.ascent = ascent;
+    }
+    if let Some(descent) = get_bdf_property(font, "FONT_DESCENT") {
+        metrics.descent = descent;
+    }
+    metrics.line_height = metrics.ascent + metrics.descent;
+    metrics.pixel_size =
+        get_bdf_property(font, "PIXEL_SIZE").unwrap_or(metrics.ascent + metrics.descent);
+    metrics
+}
+
+fn get_ttf_metrics(data: &[u8], size: usize) -> FontMetrics {
+    let sfnt = ttf::Sfnt::new(data);
+    match (sfnt.get_units_per_em(), sfnt.get_horizontal_header()) {
+        (Some(units_per_em), Some(hhea)) if units_per_em > 0 => {
+            let scale = |v: i16| (v as f32 * size as f32 / units_per_em as f32).round() as i16;
+            let ascent = scale(hhea.ascender);
+            let descent = -scale(hhea.descender);
+            FontMetrics {
+                ascent,
+                descent,
+                line_height: ascent + descent + scale(hhea.line_gap),
+                pixel_size: size as i16,
+            }
+        }
+        _ => {
+            // Typical proportions for fonts without hhea table.
+            let ascent = (size * 4 / 5) as i16;
+            FontMetrics {
+                ascent,
+                descent: size as i16 - ascent,
+                line_height: size as i16,
+                pixel_size: size as i16,
+            }
+        }
+    }
 }
 
-fn render_glyph(font: &font_rs::font::Font, codepoint: u32, size: usize) -> Option<Glyph> {
-    let id = font.lookup_glyph_id(codepoint)?;
+// Options that control how anti-aliased TTF glyphs are converted to
+// monochrome bitmaps.
+#[derive(Clone, Copy)]
+pub struct TtfRenderOptions {
+    // Pixels with coverage above this level are set. Lower values keep thin
+    // strokes visible at small sizes. Ignored when |dither| is set.
+    pub threshold: u8,
+
+    // Number of pixels by which strokes are widened horizontally. Advance
+    // widths grow by the same amount.
+    pub embolden: usize,
+
+    // When set, partially covered pixels are rendered with ordered
+    // dithering instead of a fixed threshold.
+    pub dither: bool,
+}
+
+impl Default for TtfRenderOptions {
+    fn default() -> TtfRenderOptions {
+        TtfRenderOptions {
+            threshold: 128,
+            embolden: 0,
+            dither: false,
+        }
+    }
+}
+
+// 4x4 Bayer matrix used for ordered dithering.
+const DITHER_MATRIX: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+fn render_glyph(
+    font: &font_rs::font::Font,
+    id: u16,
+    size: usize,
+    options: &TtfRenderOptions,
+) -> Option<Glyph> {
     let glyph = font.render_glyph(id, size as u32)?;
-    let width = glyph.width;
+    let width = glyph.width + options.embolden;
     let height = glyph.height;
+
+    // Returns coverage of the pixel, taking emboldening into account.
+    let get_coverage = |x: usize, y: usize| -> u8 {
+        let first = x.saturating_sub(options.embolden);
+        let last = cmp::min(x + 1, glyph.width);
+        (first..last)
+            .map(|x| glyph.data[x + y * glyph.width])
+            .max()
+            .unwrap_or(0)
+    };
+
     let rows = (height + 7) / 8;
     let mut data = Vec::with_capacity(width * rows);
     for row in 0..rows {
@@ -192,7 +693,12 @@ fn render_glyph(font: &font_rs::font::Font, codepoint: u32, size: usize) -> Opti
         for x in 0..width {
             let mut v = 0u8;
             for b in 0..cmp::min(height - y, 8) {
-                if glyph.data[x + (y + b) * width] > 128 {
+                let threshold = if options.dither {
+                    DITHER_MATRIX[(y + b) % 4][x % 4] * 16 + 8
+                } else {
+                    options.threshold
+                };
+                if get_coverage(x, y + b) > threshold {
                     v |= 1 << b;
                 }
             }
@@ -201,8 +707,9 @@ fn render_glyph(font: &font_rs::font::Font, codepoint: u32, size: usize) -> Opti
     }
     Some(Glyph {
         rect: Rect::xywh(glyph.left as i16, glyph.top as i16, width, height),
-        width: width + 1,
-        data,
+        advance: (width + 1) as f32,
+        id,
+        data: Cow::Owned(data),
     })
 }
 
@@ -231,35 +738,412 @@ impl Font {
             }
             let glyph = Glyph {
                 rect,
-                width: bdf_glyph.device_width().unwrap_or(&(width as u32, 0)).0 as usize,
-                data,
+                advance: bdf_glyph.device_width().unwrap_or(&(width as u32, 0)).0 as f32,
+                id: 0,
+                data: Cow::Owned(data),
             };
-            glyphs.insert(*codepoint as u32, glyph);
+            glyphs.insert(*codepoint as u32, Arc::new(glyph));
         }
-        Ok(Font { glyphs })
+        let metrics = get_bdf_metrics(&bdf_font, &glyphs);
+        let replacement = match get_bdf_char_property(&bdf_font, "DEFAULT_CHAR") {
+            Some(c) if glyphs.contains_key(&(c as u32)) => Replacement::Char(c),
+            _ => Replacement::Box,
+        };
+        Ok(Font::new(GlyphSource::Bitmap(glyphs), metrics, replacement))
     }
 
-    pub fn load_ttf(filename: &str, size: usize) -> Result<Font> {
+    // PCF files must be uncompressed.
+    pub fn load_pcf(filename: &str) -> Result<Font> {
         let mut file = fs::File::open(filename)?;
         let mut data = Vec::new();
         file.read_to_end(&mut data)?;
-        Font::from_data_ttf(&data[..], size)
+        Font::from_data_pcf(&data[..])
     }
 
-    pub fn from_data_ttf(data: &[u8], size: usize) -> Result<Font> {
-        let font = font_rs::font::parse(&data)?;
+    pub fn from_data_pcf(data: &[u8]) -> Result<Font> {
+        Ok(Font::from_bitmap_font(bitmap_font::parse_pcf(data)?))
+    }
+
+    // Loads PSF1 or PSF2 console font.
+    pub fn load_psf(filename: &str) -> Result<Font> {
+        let mut file = fs::File::open(filename)?;
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        Font::from_data_psf(&data[..])
+    }
+
+    pub fn from_data_psf(data: &[u8]) -> Result<Font> {
+        Ok(Font::from_bitmap_font(bitmap_font::parse_psf(data)?))
+    }
+
+    // Loads u8g2 font from C source file, as distributed with u8g2.
+    pub fn load_u8g2(filename: &str) -> Result<Font> {
+        let mut file = fs::File::open(filename)?;
+        let mut source = String::new();
+        file.read_to_string(&mut source)?;
+        Font::from_data_u8g2(&bitmap_font::parse_c_string(&source)?[..])
+    }
+
+    // Creates font from u8g2 font data, i.e. content of the C string.
+    pub fn from_data_u8g2(data: &[u8]) -> Result<Font> {
+        Ok(Font::from_bitmap_font(bitmap_font::parse_u8g2(data)?))
+    }
+
+    // Loads Adafruit GFX font from C header file.
+    pub fn load_adafruit_gfx(filename: &str) -> Result<Font> {
+        let mut file = fs::File::open(filename)?;
+        let mut source = String::new();
+        file.read_to_string(&mut source)?;
+        Font::from_source_adafruit_gfx(&source)
+    }
+
+    pub fn from_source_adafruit_gfx(source: &str) -> Result<Font> {
+        Ok(Font::from_bitmap_font(bitmap_font::parse_adafruit_gfx(
+            source,
+        )?))
+    }
+
+    fn from_bitmap_font(font: bitmap_font::BitmapFont) -> Font {
         let mut glyphs = BTreeMap::new();
-        for i in 16..2000 {
-            if let Some(g) = render_glyph(&font, i, size) {
-                glyphs.insert(i, g);
+        for g in font.glyphs {
+            let mut data = vec![0u8; g.width * ((g.height + 7) / 8)];
+            for y in 0..g.height {
+                for x in 0..g.width {
+                    if g.pixels[x + y * g.width] {
+                        data[x + (y / 8) * g.width] |= 1 << (y % 8);
+                    }
+                }
+            }
+            let glyph = Glyph {
+                rect: Rect::xywh(g.left, g.top, g.width, g.height),
+                advance: g.advance as f32,
+                id: 0,
+                data: Cow::Owned(data),
+            };
+            glyphs.insert(g.codepoint, Arc::new(glyph));
+        }
+
+        let mut metrics = get_glyphs_metrics(&glyphs);
+        metrics.ascent = font.ascent.unwrap_or(metrics.ascent);
+        metrics.descent = font.descent.unwrap_or(metrics.descent);
+        metrics.line_height = font.line_height.unwrap_or(metrics.ascent + metrics.descent);
+        metrics.pixel_size = font.pixel_size.unwrap_or(metrics.ascent + metrics.descent);
+
+        let replacement = match font.default_char.and_then(std::char::from_u32) {
+            Some(c) if glyphs.contains_key(&(c as u32)) => Replacement::Char(c),
+            _ => Replacement::Box,
+        };
+        Font::new(GlyphSource::Bitmap(glyphs), metrics, replacement)
+    }
+
+    pub fn load_ttf(filename: &str, size: usize, options: TtfRenderOptions) -> Result<Font> {
+        let mut file = fs::File::open(filename)?;
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        Font::from_data_ttf(&data[..], size, options)
+    }
+
+    // Glyphs are rasterised when they are used for the first time and kept
+    // in a cache, see set_glyph_cache_size().
+    pub fn from_data_ttf(data: &[u8], size: usize, options: TtfRenderOptions) -> Result<Font> {
+        // Parse the font to make sure it's valid.
+        font_rs::font::parse(data)?;
+        let metrics = get_ttf_metrics(data, size);
+        let sfnt = ttf::Sfnt::new(data);
+        let units_per_em = sfnt.get_units_per_em().unwrap_or(0);
+        let outline = OutlineFont {
+            data: data.to_vec(),
+            size,
+            options,
+            scale: if units_per_em > 0 {
+                size as f32 / units_per_em as f32
+            } else {
+                0.0
+            },
+            kerning: sfnt.get_kerning_tables(),
+            cache: Mutex::new(GlyphCache::new(DEFAULT_GLYPH_CACHE_SIZE)),
+        };
+
+        Ok(Font::new(
+            GlyphSource::Outline(outline),
+            metrics,
+            Replacement::Box,
+        ))
+    }
+
+    fn new(glyphs: GlyphSource, metrics: FontMetrics, replacement: Replacement) -> Font {
+        Font {
+            glyphs,
+            metrics,
+            replacement,
+            box_glyph: Arc::new(make_box_glyph(&metrics)),
+            fallbacks: Vec::new(),
+        }
+    }
+
+    // Sets maximum number of rasterised glyphs kept in memory. Has no effect
+    // for bitmap fonts.
+    pub fn set_glyph_cache_size(&mut self, size: usize) {
+        if let GlyphSource::Outline(ref outline) = self.glyphs {
+            outline.cache.lock().unwrap().set_capacity(size);
+        }
+    }
+
+    // Sets glyph that is drawn for missing characters. By default BDF fonts
+    // use their DEFAULT_CHAR and other fonts use Replacement::Box.
+    pub fn set_replacement(&mut self, replacement: Replacement) {
+        self.replacement = replacement;
+    }
+
+    pub fn replacement(&self) -> Replacement {
+        self.replacement
+    }
+
+    // Looks up glyph in this font and its fallbacks. Returns the glyph
+    // together with the font it was found in.
+    fn find_glyph(&self, codepoint: u32) -> Option<(&Font, GlyphRef)> {
+        let glyph = match self.glyphs {
+            GlyphSource::Bitmap(ref glyphs) => {
+                glyphs.get(&codepoint).cloned().map(GlyphRef::Shared)
             }
+            GlyphSource::Outline(ref outline) => outline.get_glyph(codepoint).map(GlyphRef::Shared),
+            GlyphSource::Static(font) => font.get_glyph(codepoint).map(GlyphRef::Static),
+        };
+        if let Some(glyph) = glyph {
+            return Some((self, glyph));
         }
+        self.fallbacks
+            .iter()
+            .filter_map(|f| f.find_glyph(codepoint))
+            .next()
+    }
+
+    // Same as find_glyph(), but returns the replacement glyph for missing
+    // characters.
+    fn get_glyph(&self, codepoint: u32) -> Option<(&Font, GlyphRef)> {
+        let glyph = self.find_glyph(codepoint);
+        if glyph.is_some() {
+            return glyph;
+        }
+        match self.replacement {
+            Replacement::Blank => None,
+            Replacement::Box => Some((self, GlyphRef::Shared(self.box_glyph.clone()))),
+            Replacement::Char(c) => Some(
+                self.find_glyph(c as u32)
+                    .unwrap_or_else(|| (self, GlyphRef::Shared(self.box_glyph.clone()))),
+            ),
+        }
+    }
+
+    // Returns horizontal adjustment between two glyphs from this font.
+    fn get_kerning(&self, left: &Glyph, right: &Glyph) -> f32 {
+        match self.glyphs {
+            GlyphSource::Outline(ref outline) if left.id != 0 && right.id != 0 => {
+                outline.get_kerning(left.id, right.id)
+            }
+            _ => 0.0,
+        }
+    }
+
+    // Returns true if |c| is in the font or one of its fallbacks.
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.find_glyph(c as u32).is_some()
+    }
+
+    // Returns all characters in the font and its fallbacks. TTF fonts are
+    // rasterised on demand, so their characters are not listed.
+    pub fn chars(&self) -> Vec<char> {
+        let mut result: Vec<char> = match self.glyphs {
+            GlyphSource::Bitmap(ref glyphs) => glyphs
+                .keys()
+                .filter_map(|&c| std::char::from_u32(c))
+                .collect(),
+            GlyphSource::Outline(_) => Vec::new(),
+            GlyphSource::Static(font) => font
+                .glyphs
+                .iter()
+                .filter_map(|g| std::char::from_u32(g.codepoint))
+                .collect(),
+        };
+        for f in self.fallbacks.iter() {
+            result.extend(f.chars());
+        }
+        result.sort();
+        result.dedup();
+        result
+    }
+
+    // Creates a font that draws glyphs directly from the static tables,
+    // without copying them.
+    pub fn from_static(font: &'static StaticFont) -> Font {
+        let metrics = FontMetrics {
+            ascent: font.ascent,
+            descent: font.descent,
+            line_height: font.line_height,
+            pixel_size: font.pixel_size,
+        };
+        let replacement = match font.default_char {
+            Some(c) => Replacement::Char(c),
+            None => Replacement::Box,
+        };
+        Font::new(GlyphSource::Static(font), metrics, replacement)
+    }
+
+    // Generates Rust source with a StaticFont called |name| that contains
+    // glyphs for |chars|. Characters missing in the font are skipped.
+    pub fn to_rust_source(&self, name: &str, chars: &[char]) -> String {
+        // StaticFont glyphs must be sorted by codepoint.
+        let mut chars = chars.to_vec();
+        chars.sort();
+        chars.dedup();
+
+        let mut glyphs = String::new();
+        let mut data: Vec<u8> = Vec::new();
+        for &c in chars.iter() {
+            let glyph = match self.find_glyph(c as u32) {
+                Some((_, glyph)) => glyph,
+                None => continue,
+            };
+            glyphs.push_str(&format!(
+                "        StaticGlyph {{ codepoint: 0x{:x}, left: {}, top: {}, width: {}, \
+                 height: {}, advance: {:?}, offset: {} }},\n",
+                c as u32,
+                glyph.rect.left(),
+                glyph.rect.top(),
+                glyph.rect.size.width,
+                glyph.rect.size.height,
+                glyph.advance,
+                data.len()
+            ));
+            data.extend_from_slice(&glyph.data[..]);
+        }
+
+        let default_char = match self.replacement {
+            Replacement::Char(c) if chars.contains(&c) => format!("Some({:?})", c),
+            _ => "None".to_string(),
+        };
+
+        let mut data_lines = String::new();
+        for line in data.chunks(16) {
+            let bytes: Vec<String> = line.iter().map(|b| format!("0x{:02x},", b)).collect();
+            data_lines.push_str(&format!("        {}\n", bytes.join(" ")));
+        }
+
+        format!(
+            "use mono_display::gfx::{{StaticFont, StaticGlyph}};\n\
+             \n\
+             pub static {}: StaticFont = StaticFont {{\n    \
+             ascent: {},\n    \
+             descent: {},\n    \
+             line_height: {},\n    \
+             pixel_size: {},\n    \
+             default_char: {},\n    \
+             glyphs: &[\n{}    ],\n    \
+             data: &[\n{}    ],\n\
+             }};\n",
+            name,
+            self.metrics.ascent,
+            self.metrics.descent,
+            self.metrics.line_height,
+            self.metrics.pixel_size,
+            default_char,
+            glyphs,
+            data_lines
+        )
+    }
+
+    pub fn metrics(&self) -> FontMetrics {
+        self.metrics
+    }
+
+    // Returns horizontal distance the pen moves when |c| is drawn.
+    pub fn advance(&self, c: char) -> i16 {
+        match self.get_glyph(c as u32) {
+            Some((_, glyph)) => glyph.advance.round() as i16,
+            None => MISSING_GLYPH_ADVANCE,
+        }
+    }
+
+    // Returns horizontal distance the pen moves when |text| is drawn.
+    pub fn measure(&self, text: &str) -> i16 {
+        iter_text_glyphs(Vector::zero(), self, text, |_, _| {}).x
+    }
+}
+
+// Glyph of a StaticFont.
+pub struct StaticGlyph {
+    pub codepoint: u32,
+    pub left: i16,
+    pub top: i16,
+    pub width: u16,
+    pub height: u16,
+    pub advance: f32,
+
+    // Offset of the glyph bitmap in StaticFont::data. Bitmaps are stored
+    // the same way as frames, as columns of 8-pixel pages.
+    pub offset: u32,
+}
+
+// Font stored in static tables, so it can be embedded in the binary. These
+// tables are generated by the font2rs tool from font files:
+//   cargo run --bin font2rs -- font.bdf MY_FONT > my_font.rs
+pub struct StaticFont {
+    pub ascent: i16,
+    pub descent: i16,
+    pub line_height: i16,
+    pub pixel_size: i16,
+    pub default_char: Option<char>,
+
+    // Sorted by codepoint.
+    pub glyphs: &'static [StaticGlyph],
+
+    pub data: &'static [u8],
+}
+
+impl StaticFont {
+    fn get_glyph(&self, codepoint: u32) -> Option<Glyph> {
+        let index = self
+            .glyphs
+            .binary_search_by_key(&codepoint, |g| g.codepoint)
+            .ok()?;
+        let g = &self.glyphs[index];
+        let width = g.width as usize;
+        let height = g.height as usize;
+        let offset = g.offset as usize;
+        let size = width * ((height + 7) / 8);
+        Some(Glyph {
+            rect: Rect::xywh(g.left, g.top, width, height),
+            advance: g.advance,
+            id: 0,
+            data: Cow::Borrowed(&self.data[offset..(offset + size)]),
+        })
+    }
+}
+
+// List of fonts that are searched in order for each character, e.g. a Latin
+// font followed by a CJK or a symbol font. Glyphs from all fonts share the
+// same baseline.
+pub struct FontStack {
+    fonts: Vec<Font>,
+}
+
+impl FontStack {
+    pub fn new(font: Font) -> FontStack {
+        FontStack { fonts: vec![font] }
+    }
 
-        Ok(Font { glyphs })
+    pub fn fallback(mut self, font: Font) -> FontStack {
+        self.fonts.push(font);
+        self
     }
 
-    fn get_glyph(&self, codepoint: u32) -> Option<&Glyph> {
-        self.glyphs.get(&codepoint)
+    // Returns a font that draws glyphs from all fonts in the stack. Metrics
+    // and the replacement glyph are taken from the first font.
+    pub fn build(self) -> Font {
+        let mut fonts = self.fonts.into_iter();
+        let mut result = fonts.next().unwrap();
+        result.fallbacks.extend(fonts);
+        result
     }
 }
 
@@ -269,30 +1153,356 @@ pub enum Color {
     Dark,
 }
 
+impl Color {
+    pub fn inverted(&self) -> Color {
+        match *self {
+            Color::Light => Color::Dark,
+            Color::Dark => Color::Light,
+        }
+    }
+}
+
+// Styles for draw_styled_text(). Fonts don't need to have bold variants:
+// bold text is emulated by drawing each glyph twice, offset by one pixel.
+#[derive(Clone, Copy, Eq, PartialEq, Default)]
+pub struct TextStyle {
+    pub bold: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+
+    // Text is cut out of a box filled with the text color.
+    pub inverse: bool,
+}
+
+// Origin and clip rect of a Canvas, both in frame coordinates.
+#[derive(Clone, Copy)]
+struct View {
+    origin: Vector,
+    clip: Rect,
+
+    // True if pushed with push_view(), false for push_clip().
+    is_view: bool,
+}
+
 pub struct Canvas {
     frame: Frame,
+
+    // Views pushed with push_clip() and push_view(). Each clip rect is
+    // already intersected with the previous one. Clips and views share the
+    // stack, so they must be popped in the reverse order of pushing.
+    views: Vec<View>,
+}
+
+// Returns mirrored version of paired punctuation, which is used in
+// right-to-left runs.
+fn get_mirrored_char(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        '\u{ab}' => '\u{bb}',
+        '\u{bb}' => '\u{ab}',
+        '\u{2039}' => '\u{203a}',
+        '\u{203a}' => '\u{2039}',
+        c => c,
+    }
 }
 
-fn iter_text_glyphs<F>(mut pos: Vector, font: &Font, text: &str, mut func: F)
+// Returns characters of |text| in the order they are drawn. The text is
+// normalized to NFC. Characters that are missing in the font are decomposed
+// when the font has all their parts, so that they can be drawn with combining
+// marks. Right-to-left runs are reversed, keeping combining marks after
+// their base characters, and brackets in them are mirrored. Arabic text is
+// not shaped, so joined forms are drawn only when they are passed as
+// presentation form characters.
+fn get_visual_chars(font: &Font, text: &str) -> Vec<char> {
+    if text.is_ascii() {
+        return text.chars().collect();
+    }
+
+    let mut normalized = String::with_capacity(text.len());
+    for c in text.nfc() {
+        if font.has_glyph(c) {
+            normalized.push(c);
+            continue;
+        }
+        let mut parts = Vec::new();
+        decompose_canonical(c, |p| parts.push(p));
+        if parts.len() > 1 && parts.iter().all(|&p| font.has_glyph(p)) {
+            normalized.extend(parts);
+        } else {
+            normalized.push(c);
+        }
+    }
+
+    let bidi = BidiInfo::new(&normalized, None);
+    if !bidi.has_rtl() {
+        return normalized.chars().collect();
+    }
+
+    let mut result = Vec::with_capacity(normalized.len());
+    for para in bidi.paragraphs.iter() {
+        let (levels, runs) = bidi.visual_runs(para, para.range.clone());
+        for run in runs {
+            let run_text = &normalized[run.clone()];
+            if !levels[run.start].is_rtl() {
+                result.extend(run_text.chars());
+                continue;
+            }
+            let mut clusters: Vec<Vec<char>> = Vec::new();
+            for c in run_text.chars() {
+                if is_combining_mark(c) && !clusters.is_empty() {
+                    clusters.last_mut().unwrap().push(c);
+                } else {
+                    clusters.push(vec![get_mirrored_char(c)]);
+                }
+            }
+            for cluster in clusters.iter().rev() {
+                result.extend(cluster.iter());
+            }
+        }
+    }
+    result
+}
+
+// Returns pen position for combining mark |c| drawn after a base glyph.
+// |pen| is the pen position after the base glyph. |cluster| is the rect of
+// the base glyph and the marks that were already placed on it; it's
+// extended to include the new mark. Marks above and below the base are
+// centered over it and moved away from it when they overlap, e.g. accents
+// drawn over capital letters. Other marks are drawn where the font puts
+// them.
+fn place_combining_mark(c: char, mark: &Glyph, pen: Vector, cluster: &mut Rect) -> Vector {
+    let m = mark.rect;
+    let center_x =
+        cluster.left() + (cluster.size.width as i16 - m.size.width as i16) / 2 - m.left();
+    let pos = match canonical_combining_class(c) {
+        230 => Vector::xy(
+            center_x,
+            pen.y + cmp::min(0, cluster.top() - 1 - (pen.y + m.bottom())),
+        ),
+        220 => Vector::xy(
+            center_x,
+            pen.y + cmp::max(0, cluster.bottom() + 1 - (pen.y + m.top())),
+        ),
+        _ => pen,
+    };
+    if m.size.width > 0 && m.size.height > 0 {
+        extend_rect(cluster, m.translate(pos));
+    }
+    pos
+}
+
+// Calls |func| for each glyph in |text|. Returns pen position after the
+// last glyph. The pen position is tracked with subpixel precision and
+// rounded for each glyph, so rounding errors don't accumulate. Combining
+// marks don't move the pen and are drawn over the preceding glyph.
+fn iter_text_glyphs<F>(pos: Vector, font: &Font, text: &str, mut func: F) -> Vector
 where
     F: FnMut(Vector, &Glyph),
 {
-    for c in text.chars() {
+    let mut x = pos.x as f32;
+    let mut prev: Option<(&Font, GlyphRef)> = None;
+    let mut cluster: Option<Rect> = None;
+    for c in get_visual_chars(font, text) {
+        if is_combining_mark(c) {
+            if let Some(ref mut cluster) = cluster {
+                // Missing marks are skipped instead of drawing a
+                // replacement glyph over the base.
+                if let Some((_, mark)) = font.find_glyph(c as u32) {
+                    let pen = Vector::xy(x.round() as i16, pos.y);
+                    func(place_combining_mark(c, &mark, pen, cluster), &mark);
+                }
+                continue;
+            }
+        }
+
         match font.get_glyph(c as u32) {
-            Some(glyph) => {
-                func(pos, glyph);
-                pos.x += glyph.width as i16;
+            Some((glyph_font, glyph)) => {
+                if let Some((prev_font, ref prev_glyph)) = prev {
+                    if ptr::eq(prev_font, glyph_font) {
+                        x += glyph_font.get_kerning(prev_glyph, &glyph);
+                    }
+                }
+                let glyph_pos = Vector::xy(x.round() as i16, pos.y);
+                func(glyph_pos, &glyph);
+                cluster = Some(glyph.rect.translate(glyph_pos));
+                x += glyph.advance;
+                prev = Some((glyph_font, glyph));
             }
             None => {
-                pos.x += 5;
+                x += MISSING_GLYPH_ADVANCE as f32;
+                prev = None;
+                cluster = None;
             }
         }
     }
+    Vector::xy(x.round() as i16, pos.y)
+}
+
+// Returns rects for underline and strikethrough lines for text drawn from
+// |pos| to |end|. Line positions and thickness are derived from the font
+// metrics.
+fn get_text_lines(pos: Vector, end: Vector, font: &Font, style: TextStyle) -> Vec<Rect> {
+    let metrics = font.metrics();
+    let thickness = cmp::max(1, metrics.pixel_size / 12);
+    let right = if style.bold { end.x + 1 } else { end.x };
+    let width = cmp::max(0, right - pos.x) as usize;
+
+    let mut result = Vec::new();
+    if style.underline {
+        let offset = cmp::max(
+            1,
+            cmp::min(metrics.descent / 2, metrics.descent - thickness),
+        );
+        result.push(Rect::xywh(pos.x, pos.y + offset, width, thickness as usize));
+    }
+    if style.strikethrough {
+        let offset = metrics.ascent / 3 + thickness / 2;
+        result.push(Rect::xywh(pos.x, pos.y - offset, width, thickness as usize));
+    }
+    result
+}
+
+// Rotates pixel offset |v| clockwise around the origin.
+fn rotate_vector(v: Vector, rotation: Rotation) -> Vector {
+    match rotation {
+        Rotation::Rotate0 => v,
+        Rotation::Rotate90 => Vector::xy(-v.y, v.x),
+        Rotation::Rotate180 => Vector::xy(-v.x, -v.y),
+        Rotation::Rotate270 => Vector::xy(v.y, -v.x),
+    }
+}
+
+// Rotates |rect| clockwise around the origin, such that it contains all
+// pixels of the original rect rotated with rotate_vector().
+fn rotate_rect(rect: Rect, rotation: Rotation) -> Rect {
+    let (l, t, r, b) = (rect.left(), rect.top(), rect.right(), rect.bottom());
+    match rotation {
+        Rotation::Rotate0 => rect,
+        Rotation::Rotate90 => Rect::ltrb(1 - b, l, 1 - t, r),
+        Rotation::Rotate180 => Rect::ltrb(1 - r, 1 - b, 1 - l, 1 - t),
+        Rotation::Rotate270 => Rect::ltrb(t, 1 - r, b, 1 - l),
+    }
+}
+
+// Extends |rect| to include |other|.
+pub(crate) fn extend_rect(rect: &mut Rect, other: Rect) {
+    let dl = rect.left() - other.left();
+    if dl > 0 {
+        rect.pos.x -= dl;
+        rect.size.width += dl as usize;
+    }
+
+    let dr = other.right() - rect.right();
+    if dr > 0 {
+        rect.size.width += dr as usize;
+    }
+
+    let dt = rect.top() - other.top();
+    if dt > 0 {
+        rect.pos.y -= dt;
+        rect.size.height += dt as usize;
+    }
+
+    let db = other.bottom() - rect.bottom();
+    if db > 0 {
+        rect.size.height += db as usize;
+    }
 }
 
 impl Canvas {
     pub fn new(frame: Frame) -> Canvas {
-        Canvas { frame }
+        Canvas {
+            frame,
+            views: Vec::new(),
+        }
+    }
+
+    fn view(&self) -> View {
+        match self.views.last() {
+            Some(&view) => view,
+            None => View {
+                origin: Vector::zero(),
+                clip: Rect::ps(Vector::zero(), self.frame.size()),
+                is_view: false,
+            },
+        }
+    }
+
+    // Restricts all drawing to |rect| until the matching pop_clip() call.
+    // Clip rects are nested: the new clip is intersected with the current
+    // one.
+    pub fn push_clip(&mut self, rect: Rect) {
+        let view = self.view();
+        self.views.push(View {
+            origin: view.origin,
+            clip: rect.translate(view.origin).intersect(view.clip),
+            is_view: false,
+        });
+    }
+
+    pub fn pop_clip(&mut self) {
+        let view = self.views.pop();
+        debug_assert!(
+            matches!(view, Some(View { is_view: false, .. })),
+            "pop_clip() without push_clip()"
+        );
+    }
+
+    // Calls |draw| with drawing restricted to |rect|, see push_clip().
+    pub fn draw_clipped<F>(&mut self, rect: Rect, draw: F)
+    where
+        F: FnOnce(&mut Canvas),
+    {
+        self.push_clip(rect);
+        draw(self);
+        self.pop_clip();
+    }
+
+    // Makes |rect| a sub-canvas until the matching pop_view() call: the
+    // top-left corner of |rect| becomes the origin and drawing is clipped
+    // to |rect|. Views can be nested.
+    pub fn push_view(&mut self, rect: Rect) {
+        let view = self.view();
+        self.views.push(View {
+            origin: view.origin + rect.pos,
+            clip: rect.translate(view.origin).intersect(view.clip),
+            is_view: true,
+        });
+    }
+
+    pub fn pop_view(&mut self) {
+        let view = self.views.pop();
+        debug_assert!(
+            matches!(view, Some(View { is_view: true, .. })),
+            "pop_view() without push_view()"
+        );
+    }
+
+    // Calls |draw| with the canvas set up as a sub-canvas for |rect|, see
+    // push_view().
+    pub fn draw_view<F>(&mut self, rect: Rect, draw: F)
+    where
+        F: FnOnce(&mut Canvas),
+    {
+        self.push_view(rect);
+        draw(self);
+        self.pop_view();
+    }
+
+    // Returns the rect drawing is currently restricted to, relative to the
+    // current origin. In a view it starts at (0, 0) and has the size of
+    // the view.
+    pub fn clip(&self) -> Rect {
+        let view = self.view();
+        view.clip
+            .translate(Vector::xy(-view.origin.x, -view.origin.y))
     }
 
     pub fn set_pixel(&mut self, point: Vector, color: Color) {
@@ -300,7 +1510,8 @@ impl Canvas {
     }
 
     pub fn draw_rect(&mut self, mut rect: Rect, color: Color) {
-        rect = rect.intersect(Rect::ps(Vector::zero(), self.frame.size()));
+        let view = self.view();
+        rect = rect.translate(view.origin).intersect(view.clip);
 
         let top = rect.top();
         let bottom = rect.bottom() - 1;
@@ -334,26 +1545,29 @@ impl Canvas {
     }
 
     fn draw_glyph(&mut self, pos: Vector, glyph: &Glyph, color: Color) {
+        let view = self.view();
+        let clip = view.clip;
+        let pos = pos + view.origin;
         let left = pos.x + glyph.rect.left();
         let top = pos.y + glyph.rect.top();
         let frame_width = self.frame.size().width as i16;
-        let x_start = cmp::max(0, -left);
-        let x_end = cmp::min(glyph.rect.size.width as i16, frame_width - left);
+        let x_start = cmp::max(0, clip.left() - left);
+        let x_end = cmp::min(glyph.rect.size.width as i16, clip.right() - left);
 
         let mut y = 0i16;
         while y < glyph.rect.size.height as i16 {
             let out_y = top + y;
-            if out_y < 0 {
+            if out_y < clip.top() {
                 y += 1;
                 continue;
             }
-            if out_y >= self.frame.size().height as i16 {
+            if out_y >= clip.bottom() {
                 break;
             }
 
             let rows_to_copy = cmp::min(
                 cmp::min(8 - (y % 8), 8 - (out_y % 8)),
-                glyph.rect.size.height as i16 - y,
+                cmp::min(glyph.rect.size.height as i16 - y, clip.bottom() - out_y),
             );
             let mask = 0xffu8 << (8 - rows_to_copy) >> (8 - rows_to_copy);
             let in_shift = y % 8;
@@ -375,40 +1589,225 @@ impl Canvas {
         }
     }
 
+    // Same as draw_glyph(), but the glyph is rotated clockwise around |pos|.
+    fn draw_rotated_glyph(&mut self, pos: Vector, glyph: &Glyph, rotation: Rotation, color: Color) {
+        if rotation == Rotation::Rotate0 {
+            self.draw_glyph(pos, glyph, color);
+            return;
+        }
+
+        let width = glyph.rect.size.width;
+        let size = self.frame.size();
+        let view = self.view();
+        let clip = view.clip;
+        let pos = pos + view.origin;
+        for y in 0..glyph.rect.size.height {
+            for x in 0..width {
+                if (glyph.data[(y / 8) * width + x] >> (y % 8)) & 1 == 0 {
+                    continue;
+                }
+                let offset = glyph.rect.pos.add_xy(x as i16, y as i16);
+                let p = pos + rotate_vector(offset, rotation);
+                if p.x < clip.left()
+                    || p.y < clip.top()
+                    || p.x >= clip.right()
+                    || p.y >= clip.bottom()
+                {
+                    continue;
+                }
+                let byte =
+                    &mut self.frame.mut_data()[(p.y as usize / 8) * size.width + p.x as usize];
+                let mask = 1u8 << (p.y % 8);
+                if color == Color::Light {
+                    *byte |= mask;
+                } else {
+                    *byte &= !mask;
+                }
+            }
+        }
+    }
+
+    // Draws |glyph| with each pixel scaled to a |scale|x|scale| square.
+    fn draw_scaled_glyph(&mut self, pos: Vector, glyph: &Glyph, scale: usize, color: Color) {
+        let width = glyph.rect.size.width;
+        let s = scale as i16;
+        for y in 0..glyph.rect.size.height {
+            for x in 0..width {
+                if (glyph.data[(y / 8) * width + x] >> (y % 8)) & 1 == 0 {
+                    continue;
+                }
+                let offset = glyph.rect.pos.add_xy(x as i16, y as i16);
+                let rect = Rect::xywh(pos.x + offset.x * s, pos.y + offset.y * s, scale, scale);
+                self.draw_rect(rect, color);
+            }
+        }
+    }
+
+    // Draws pixels that are set in |bitmap| with |color| at |pos|. Other
+    // pixels are left unchanged.
+    pub fn draw_bitmap(&mut self, pos: Vector, bitmap: &Frame, color: Color) {
+        self.draw_scaled_bitmap(pos, bitmap, 1, color)
+    }
+
+    // Same as draw_bitmap(), but each pixel is drawn as a |scale|x|scale|
+    // square.
+    pub fn draw_scaled_bitmap(&mut self, pos: Vector, bitmap: &Frame, scale: usize, color: Color) {
+        let size = bitmap.size();
+        let s = scale as i16;
+        for y in 0..size.height {
+            for x in 0..size.width {
+                if bitmap.get_pixel(x, y) {
+                    let rect = Rect::xywh(pos.x + x as i16 * s, pos.y + y as i16 * s, scale, scale);
+                    self.draw_rect(rect, color);
+                }
+            }
+        }
+    }
+
     pub fn draw_text(&mut self, pos: Vector, font: &Font, text: &str, color: Color) {
         iter_text_glyphs(pos, font, text, |pos, glyph| {
             self.draw_glyph(pos, glyph, color)
         });
     }
 
-    pub fn get_text_rect(&self, pos: Vector, font: &Font, text: &str) -> Rect {
-        let mut rect = Rect::ps(pos, Size::zero());
-        iter_text_glyphs(pos, font, text, |pos, glyph| {
-            let g_rect = glyph.rect.translate(pos);
+    pub fn draw_styled_text(
+        &mut self,
+        pos: Vector,
+        font: &Font,
+        text: &str,
+        style: TextStyle,
+        color: Color,
+    ) {
+        let color = if style.inverse {
+            let rect = self.get_styled_text_rect(pos, font, text, style);
+            self.draw_rect(rect, color);
+            color.inverted()
+        } else {
+            color
+        };
 
-            let dl = rect.left() - g_rect.left();
-            if dl > 0 {
-                rect.pos.x -= dl;
-                rect.size.width += dl as usize;
+        let end = iter_text_glyphs(pos, font, text, |pos, glyph| {
+            self.draw_glyph(pos, glyph, color);
+            if style.bold {
+                self.draw_glyph(pos.add_xy(1, 0), glyph, color);
             }
+        });
 
-            let dr = g_rect.right() - rect.right();
-            if dr > 0 {
-                rect.size.width += dr as usize;
-            }
+        for line in get_text_lines(pos, end, font, style) {
+            self.draw_rect(line, color);
+        }
+    }
 
-            let dt = rect.top() - g_rect.top();
-            if dt > 0 {
-                rect.pos.y -= dt;
-                rect.size.height += dt as usize;
-            }
+    // Draws |text| rotated clockwise around |pos|. E.g. with Rotate90 the
+    // text goes down and with Rotate270 it goes up, which is what vertical
+    // axis labels usually use.
+    pub fn draw_rotated_text(
+        &mut self,
+        pos: Vector,
+        font: &Font,
+        text: &str,
+        rotation: Rotation,
+        color: Color,
+    ) {
+        iter_text_glyphs(Vector::zero(), font, text, |pen, glyph| {
+            self.draw_rotated_glyph(pos + rotate_vector(pen, rotation), glyph, rotation, color)
+        });
+    }
 
-            let db = g_rect.bottom() - rect.bottom();
-            if db > 0 {
-                rect.size.height += db as usize;
+    pub fn get_rotated_text_rect(
+        &self,
+        pos: Vector,
+        font: &Font,
+        text: &str,
+        rotation: Rotation,
+    ) -> Rect {
+        let rect = self.get_text_rect(Vector::zero(), font, text);
+        rotate_rect(rect, rotation).translate(pos)
+    }
+
+    // Draws |text| with upright characters stacked from top to bottom.
+    // |pos| is the top of the column, characters are centered around
+    // |pos.x|.
+    pub fn draw_vertical_text(&mut self, pos: Vector, font: &Font, text: &str, color: Color) {
+        let metrics = font.metrics();
+        let mut y = pos.y + metrics.ascent;
+        for c in text.chars() {
+            let mut buf = [0u8; 4];
+            let x = pos.x - font.advance(c) / 2;
+            self.draw_text(Vector::xy(x, y), font, c.encode_utf8(&mut buf), color);
+            y += metrics.line_height;
+        }
+    }
+
+    pub fn get_vertical_text_rect(&self, pos: Vector, font: &Font, text: &str) -> Rect {
+        let metrics = font.metrics();
+        let mut rect = Rect::ps(pos, Size::zero());
+        let mut y = pos.y + metrics.ascent;
+        for c in text.chars() {
+            let mut buf = [0u8; 4];
+            let x = pos.x - font.advance(c) / 2;
+            extend_rect(
+                &mut rect,
+                self.get_text_rect(Vector::xy(x, y), font, c.encode_utf8(&mut buf)),
+            );
+            y += metrics.line_height;
+        }
+        rect
+    }
+
+    // Draws |text| with the font scaled |scale| times. Pixels are scaled
+    // with nearest-neighbour, so bitmap fonts stay crisp. |pos| is on the
+    // baseline.
+    pub fn draw_scaled_text(
+        &mut self,
+        pos: Vector,
+        font: &Font,
+        text: &str,
+        scale: usize,
+        color: Color,
+    ) {
+        let s = scale as i16;
+        iter_text_glyphs(Vector::zero(), font, text, |pen, glyph| {
+            let glyph_pos = Vector::xy(pos.x + pen.x * s, pos.y + pen.y * s);
+            self.draw_scaled_glyph(glyph_pos, glyph, scale, color)
+        });
+    }
+
+    pub fn get_scaled_text_rect(&self, pos: Vector, font: &Font, text: &str, scale: usize) -> Rect {
+        let rect = self.get_text_rect(Vector::zero(), font, text);
+        let s = scale as i16;
+        Rect::xywh(
+            pos.x + rect.left() * s,
+            pos.y + rect.top() * s,
+            rect.size.width * scale,
+            rect.size.height * scale,
+        )
+    }
+
+    pub fn get_text_rect(&self, pos: Vector, font: &Font, text: &str) -> Rect {
+        self.get_styled_text_rect(pos, font, text, TextStyle::default())
+    }
+
+    pub fn get_styled_text_rect(
+        &self,
+        pos: Vector,
+        font: &Font,
+        text: &str,
+        style: TextStyle,
+    ) -> Rect {
+        let mut rect = Rect::ps(pos, Size::zero());
+        let end = iter_text_glyphs(pos, font, text, |pos, glyph| {
+            let mut g_rect = glyph.rect.translate(pos);
+            if style.bold && g_rect.size.width > 0 {
+                g_rect.size.width += 1;
             }
+            extend_rect(&mut rect, g_rect);
         });
 
+        for line in get_text_lines(pos, end, font, style) {
+            extend_rect(&mut rect, line);
+        }
+
         rect
     }
 
@@ -0,0 +1,135 @@
+extern crate rppal;
+
+use self::rppal::i2c;
+use super::*;
+use led_matrix::MatrixLayout;
+
+#[derive(Clone)]
+pub struct Ht16k33Config {
+    // Native orientation of each module is with row 0 connected to COM0 and
+    // column 0 connected to ROW0, as on Adafruit 8x8 backpacks.
+    pub layout: MatrixLayout,
+
+    // I2C address of each module in the layout.
+    pub addresses: Vec<u16>,
+
+    // Brightness, 0-15.
+    pub brightness: u8,
+}
+
+impl Ht16k33Config {
+    pub fn new(layout: MatrixLayout, addresses: Vec<u16>) -> Ht16k33Config {
+        Ht16k33Config {
+            layout,
+            addresses,
+            brightness: 8,
+        }
+    }
+}
+
+pub struct Ht16k33 {
+    config: Ht16k33Config,
+    bus: i2c::I2c,
+    modules: Vec<Option<[u8; 8]>>,
+    old_frame: Option<gfx::Frame>,
+}
+
+const COMMAND_OSCILLATOR_ON: u8 = 0x21;
+const COMMAND_DISPLAY_ON: u8 = 0x81;
+const COMMAND_BRIGHTNESS: u8 = 0xE0;
+
+impl Ht16k33 {
+    pub fn new(config: Ht16k33Config) -> Result<Ht16k33> {
+        config.layout.validate()?;
+        if config.layout.modules.len() != config.addresses.len() {
+            return Err(Error::new(
+                "Number of addresses doesn't match number of modules.",
+            ));
+        }
+        let bus = i2c::I2c::new()?;
+        let num_modules = config.addresses.len();
+        let mut result = Ht16k33 {
+            config,
+            bus,
+            modules: vec![None; num_modules],
+            old_frame: None,
+        };
+        result.initialize()?;
+
+        Ok(result)
+    }
+
+    fn initialize(&mut self) -> Result<()> {
+        for i in 0..self.modules.len() {
+            self.send_command(i, &[COMMAND_OSCILLATOR_ON])?;
+            self.send_command(i, &[COMMAND_DISPLAY_ON])?;
+        }
+        let brightness = self.config.brightness;
+        self.set_brightness(brightness)
+    }
+
+    pub fn set_brightness(&mut self, brightness: u8) -> Result<()> {
+        if brightness > 15 {
+            return Err(Error::new("Brightness must be in 0-15 range."));
+        }
+        self.config.brightness = brightness;
+        for i in 0..self.modules.len() {
+            self.send_command(i, &[COMMAND_BRIGHTNESS | brightness])?;
+        }
+        Ok(())
+    }
+
+    fn send_command(&mut self, module: usize, data: &[u8]) -> Result<()> {
+        self.bus.set_slave_address(self.config.addresses[module])?;
+        assert!(self.bus.write(data)? == data.len());
+        Ok(())
+    }
+
+    fn show_frame_internal(&mut self, frame: gfx::Frame) -> Result<()> {
+        assert!(frame.size() == self.size());
+
+        for i in 0..self.modules.len() {
+            let rows = self.config.layout.get_module_rows(&frame, i);
+            if self.modules[i] == Some(rows) {
+                continue;
+            }
+
+            // Display RAM has 16 bits for each COM line, i.e. for each row.
+            // Bit N drives ROWN, only the lower 8 are used by 8x8 modules.
+            let mut data = Vec::with_capacity(17);
+            data.push(0x00);
+            for row in rows.iter() {
+                data.push(*row);
+                data.push(0x00);
+            }
+            self.send_command(i, &data[..])?;
+            self.modules[i] = Some(rows);
+        }
+
+        self.old_frame = Some(frame);
+
+        Ok(())
+    }
+}
+
+impl DisplayDriver for Ht16k33 {
+    fn size(&self) -> gfx::Size {
+        self.config.layout.size()
+    }
+
+    fn show_frame(&mut self, frame: gfx::Frame) {
+        if let Err(e) = self.show_frame_internal(frame) {
+            println!("Ht16k33: Failed to push a frame: {}", e.msg);
+        }
+    }
+
+    fn get_frame(&mut self) -> gfx::Frame {
+        match self.old_frame.take() {
+            Some(mut f) => {
+                f.clear();
+                f
+            }
+            None => gfx::Frame::new(self.size()),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use super::*;
+
+// Position of an 8x8 LED module in a matrix display.
+#[derive(Clone, Copy)]
+pub struct ModulePlacement {
+    // Position of the module in the grid, in modules.
+    pub column: usize,
+    pub row: usize,
+
+    // Physical rotation of the module relative to its native orientation,
+    // i.e. with row 0 on top and column 0 on the left.
+    pub rotation: gfx::Rotation,
+}
+
+// Arrangement of 8x8 modules in a display. Modules are listed in the order
+// in which they are connected: chain order for MAX7219 and the order of
+// addresses for HT16K33.
+#[derive(Clone)]
+pub struct MatrixLayout {
+    pub columns: usize,
+    pub rows: usize,
+    pub modules: Vec<ModulePlacement>,
+}
+
+impl MatrixLayout {
+    // Layout for a grid of modules that are all rotated the same way and
+    // connected row by row, starting from the top-left module.
+    pub fn grid(columns: usize, rows: usize, rotation: gfx::Rotation) -> MatrixLayout {
+        let mut modules = Vec::with_capacity(columns * rows);
+        for row in 0..rows {
+            for column in 0..columns {
+                modules.push(ModulePlacement {
+                    column,
+                    row,
+                    rotation,
+                });
+            }
+        }
+        MatrixLayout {
+            columns,
+            rows,
+            modules,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.modules.is_empty() {
+            return Err(Error::new("Matrix layout is empty."));
+        }
+        for m in self.modules.iter() {
+            if m.column >= self.columns || m.row >= self.rows {
+                return Err(Error::new("Matrix module is outside of the layout."));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn size(&self) -> gfx::Size {
+        gfx::Size::wh(self.columns * 8, self.rows * 8)
+    }
+
+    // Returns content of the module with the specified |index| as 8 rows in
+    // the native orientation of the module. Bit N in each row corresponds to
+    // column N.
+    pub fn get_module_rows(&self, frame: &gfx::Frame, index: usize) -> [u8; 8] {
+        let module = &self.modules[index];
+        let left = module.column * 8;
+        let top = module.row * 8;
+        let mut result = [0u8; 8];
+        for (y, row) in result.iter_mut().enumerate() {
+            for x in 0..8 {
+                let (fx, fy) = match module.rotation {
+                    gfx::Rotation::Rotate0 => (x, y),
+                    gfx::Rotation::Rotate90 => (7 - y, x),
+                    gfx::Rotation::Rotate180 => (7 - x, 7 - y),
+                    gfx::Rotation::Rotate270 => (y, 7 - x),
+                };
+                if frame.get_pixel(left + fx, top + fy) {
+                    *row |= 1 << x;
+                }
+            }
+        }
+        result
+    }
+}
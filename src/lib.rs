@@ -1,6 +1,20 @@
+mod bitmap_font;
+pub mod compositor;
 mod driver;
+pub mod epaper;
 pub mod gfx;
+pub mod ht16k33;
+pub mod led_matrix;
+pub mod max7219;
+pub mod rotated_driver;
+pub mod segment;
+pub mod sharp_memory;
+mod spi_bus;
 pub mod ssd1306;
+pub mod st7565;
+pub mod text;
+pub mod tft;
+mod ttf;
 
 #[cfg(feature = "sdl2")]
 pub mod sdl_driver;
@@ -25,3 +39,4 @@ impl Error {
 
 pub type Result<T> = result::Result<T, Error>;
 pub use self::driver::DisplayDriver;
+pub use self::spi_bus::{Bus, SlaveSelect, SpiConfig};
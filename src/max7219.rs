@@ -0,0 +1,167 @@
+extern crate rppal;
+
+use self::rppal::spi;
+use super::*;
+use led_matrix::MatrixLayout;
+
+#[derive(Clone)]
+pub struct Max7219Config {
+    pub bus: spi::Bus,
+    pub slave_select: spi::SlaveSelect,
+    pub clock_speed: u32,
+
+    // Native orientation of each module is with digit 0 as the top row and
+    // segment DP as the left column.
+    pub layout: MatrixLayout,
+
+    // Brightness, 0-15.
+    pub intensity: u8,
+}
+
+impl Max7219Config {
+    pub fn new(layout: MatrixLayout) -> Max7219Config {
+        Max7219Config {
+            bus: spi::Bus::Spi0,
+            slave_select: spi::SlaveSelect::Ss0,
+            clock_speed: 1_000_000,
+            layout,
+            intensity: 4,
+        }
+    }
+}
+
+pub struct Max7219 {
+    config: Max7219Config,
+    spi: spi::Spi,
+
+    // Content of the digit registers for each module.
+    modules: Vec<[u8; 8]>,
+    old_frame: Option<gfx::Frame>,
+}
+
+const REGISTER_NOOP: u8 = 0x00;
+const REGISTER_DIGIT0: u8 = 0x01;
+const REGISTER_DECODE_MODE: u8 = 0x09;
+const REGISTER_INTENSITY: u8 = 0x0A;
+const REGISTER_SCAN_LIMIT: u8 = 0x0B;
+const REGISTER_SHUTDOWN: u8 = 0x0C;
+const REGISTER_DISPLAY_TEST: u8 = 0x0F;
+
+impl Max7219 {
+    pub fn new(config: Max7219Config) -> Result<Max7219> {
+        config.layout.validate()?;
+        let spi = spi::Spi::new(
+            config.bus,
+            config.slave_select,
+            config.clock_speed,
+            spi::Mode::Mode0,
+        )?;
+        let num_modules = config.layout.modules.len();
+        let mut result = Max7219 {
+            config,
+            spi,
+            modules: vec![[0u8; 8]; num_modules],
+            old_frame: None,
+        };
+        result.initialize()?;
+
+        Ok(result)
+    }
+
+    fn initialize(&mut self) -> Result<()> {
+        self.set_all(REGISTER_DISPLAY_TEST, 0)?;
+        self.set_all(REGISTER_DECODE_MODE, 0)?;
+        self.set_all(REGISTER_SCAN_LIMIT, 7)?;
+        let intensity = self.config.intensity;
+        self.set_intensity(intensity)?;
+        for row in 0..8 {
+            self.set_all(REGISTER_DIGIT0 + row, 0)?;
+        }
+        self.set_all(REGISTER_SHUTDOWN, 1)?;
+        Ok(())
+    }
+
+    pub fn set_intensity(&mut self, intensity: u8) -> Result<()> {
+        if intensity > 15 {
+            return Err(Error::new("Intensity must be in 0-15 range."));
+        }
+        self.config.intensity = intensity;
+        self.set_all(REGISTER_INTENSITY, intensity)
+    }
+
+    // Sends one register write per module. Data shifts through the chain,
+    // so the value for the last module is sent first. Modules that don't
+    // need an update get a no-op.
+    fn write_registers(&mut self, values: &[Option<(u8, u8)>]) -> Result<()> {
+        let mut data = Vec::with_capacity(values.len() * 2);
+        for v in values.iter().rev() {
+            let (register, value) = v.unwrap_or((REGISTER_NOOP, 0));
+            data.push(register);
+            data.push(value);
+        }
+        assert!(self.spi.write(&data[..])? == data.len());
+        Ok(())
+    }
+
+    fn set_all(&mut self, register: u8, value: u8) -> Result<()> {
+        let values = vec![Some((register, value)); self.modules.len()];
+        self.write_registers(&values[..])
+    }
+
+    fn show_frame_internal(&mut self, frame: gfx::Frame) -> Result<()> {
+        assert!(frame.size() == self.size());
+
+        let new_modules: Vec<[u8; 8]> = (0..self.modules.len())
+            .map(|i| {
+                let mut rows = self.config.layout.get_module_rows(&frame, i);
+                spi::reverse_bits(&mut rows[..]);
+                rows
+            })
+            .collect();
+
+        for row in 0..8 {
+            let values: Vec<Option<(u8, u8)>> = self
+                .modules
+                .iter()
+                .zip(new_modules.iter())
+                .map(|(old, new)| {
+                    if old[row] != new[row] {
+                        Some((REGISTER_DIGIT0 + row as u8, new[row]))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            if values.iter().any(|v| v.is_some()) {
+                self.write_registers(&values[..])?;
+            }
+        }
+
+        self.modules = new_modules;
+        self.old_frame = Some(frame);
+
+        Ok(())
+    }
+}
+
+impl DisplayDriver for Max7219 {
+    fn size(&self) -> gfx::Size {
+        self.config.layout.size()
+    }
+
+    fn show_frame(&mut self, frame: gfx::Frame) {
+        if let Err(e) = self.show_frame_internal(frame) {
+            println!("Max7219: Failed to push a frame: {}", e.msg);
+        }
+    }
+
+    fn get_frame(&mut self) -> gfx::Frame {
+        match self.old_frame.take() {
+            Some(mut f) => {
+                f.clear();
+                f
+            }
+            None => gfx::Frame::new(self.size()),
+        }
+    }
+}
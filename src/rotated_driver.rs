@@ -0,0 +1,57 @@
+use super::*;
+
+// Wraps a DisplayDriver to rotate frames in software, e.g. for panels that
+// are mounted in portrait orientation. Frames passed to show_frame() are
+// in the rotated orientation, so size() has width and height swapped for
+// 90 and 270 degree rotations.
+pub struct RotatedDriver<D: DisplayDriver> {
+    driver: D,
+    rotation: gfx::Rotation,
+    old_frame: Option<gfx::Frame>,
+}
+
+impl<D: DisplayDriver> RotatedDriver<D> {
+    pub fn new(driver: D, rotation: gfx::Rotation) -> RotatedDriver<D> {
+        RotatedDriver {
+            driver,
+            rotation,
+            old_frame: None,
+        }
+    }
+
+    pub fn driver(&mut self) -> &mut D {
+        &mut self.driver
+    }
+
+    pub fn into_driver(self) -> D {
+        self.driver
+    }
+}
+
+impl<D: DisplayDriver> DisplayDriver for RotatedDriver<D> {
+    fn size(&self) -> gfx::Size {
+        let size = self.driver.size();
+        match self.rotation {
+            gfx::Rotation::Rotate0 | gfx::Rotation::Rotate180 => size,
+            gfx::Rotation::Rotate90 | gfx::Rotation::Rotate270 => {
+                gfx::Size::wh(size.height, size.width)
+            }
+        }
+    }
+
+    fn show_frame(&mut self, frame: gfx::Frame) {
+        assert!(frame.size() == self.size());
+        self.driver.show_frame(frame.rotate(self.rotation));
+        self.old_frame = Some(frame);
+    }
+
+    fn get_frame(&mut self) -> gfx::Frame {
+        match self.old_frame.take() {
+            Some(mut f) => {
+                f.clear();
+                f
+            }
+            None => gfx::Frame::new(self.size()),
+        }
+    }
+}
@@ -0,0 +1,432 @@
+use gfx::{Canvas, Color, Rect, Size, Vector};
+use std::cmp;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum SegmentType {
+    // Digits and a few letters.
+    Seven,
+
+    // Digits and all Latin letters.
+    Fourteen,
+}
+
+#[derive(Clone, Copy)]
+pub struct SegmentStyle {
+    pub segment_type: SegmentType,
+
+    // Size of each digit, not including slant.
+    pub digit_size: Size,
+
+    // Segment thickness in pixels.
+    pub thickness: usize,
+
+    // Horizontal offset of the top of the digits relative to their bottom,
+    // in pixels. Positive values slant digits to the right.
+    pub slant: i16,
+
+    // Space between adjacent segments.
+    pub gap: usize,
+
+    // Space between characters.
+    pub spacing: usize,
+}
+
+impl SegmentStyle {
+    pub fn new(segment_type: SegmentType, height: usize) -> SegmentStyle {
+        // Fourteen-segment digits are wider and thinner to leave space for
+        // diagonals.
+        let (width, thickness) = match segment_type {
+            SegmentType::Seven => (height / 2, height / 8),
+            SegmentType::Fourteen => (height * 3 / 4, height / 10),
+        };
+        SegmentStyle {
+            segment_type,
+            digit_size: Size::wh(width, height),
+            thickness: cmp::max(1, thickness),
+            slant: 0,
+            gap: if height >= 16 { 1 } else { 0 },
+            spacing: cmp::max(2, height / 8),
+        }
+    }
+}
+
+// Segments. G1 and G2 are halves of the middle segment, they are drawn as a
+// single segment in seven-segment mode. H, J, K and M are diagonals, I and L
+// are vertical segments in the middle.
+const A: u16 = 1 << 0;
+const B: u16 = 1 << 1;
+const C: u16 = 1 << 2;
+const D: u16 = 1 << 3;
+const E: u16 = 1 << 4;
+const F: u16 = 1 << 5;
+const G1: u16 = 1 << 6;
+const G2: u16 = 1 << 7;
+const H: u16 = 1 << 8;
+const I: u16 = 1 << 9;
+const J: u16 = 1 << 10;
+const K: u16 = 1 << 11;
+const L: u16 = 1 << 12;
+const M: u16 = 1 << 13;
+const G: u16 = G1 | G2;
+
+fn get_common_segments(c: char) -> Option<u16> {
+    let segments = match c {
+        '0' => A | B | C | D | E | F,
+        '1' => B | C,
+        '2' => A | B | D | E | G,
+        '3' => A | B | C | D | G,
+        '4' => B | C | F | G,
+        '5' => A | C | D | F | G,
+        '6' => A | C | D | E | F | G,
+        '7' => A | B | C,
+        '8' => A | B | C | D | E | F | G,
+        '9' => A | B | C | D | F | G,
+        '-' => G,
+        '_' => D,
+        '=' => D | G,
+        '\u{b0}' => A | B | F | G,
+        ' ' => 0,
+        _ => return None,
+    };
+    Some(segments)
+}
+
+// Seven-segment letters. Most of them can be displayed in one case only.
+fn lookup_seven_segments(c: char) -> Option<u16> {
+    match c {
+        'A' => Some(A | B | C | E | F | G),
+        'b' => Some(C | D | E | F | G),
+        'C' => Some(A | D | E | F),
+        'c' => Some(D | E | G),
+        'd' => Some(B | C | D | E | G),
+        'E' => Some(A | D | E | F | G),
+        'F' => Some(A | E | F | G),
+        'G' => Some(A | C | D | E | F),
+        'H' => Some(B | C | E | F | G),
+        'h' => Some(C | E | F | G),
+        'I' => Some(E | F),
+        'J' => Some(B | C | D | E),
+        'L' => Some(D | E | F),
+        'n' => Some(C | E | G),
+        'o' => Some(C | D | E | G),
+        'P' => Some(A | B | E | F | G),
+        'q' => Some(A | B | C | F | G),
+        'r' => Some(E | G),
+        'S' => Some(A | C | D | F | G),
+        't' => Some(D | E | F | G),
+        'U' => Some(B | C | D | E | F),
+        'u' => Some(C | D | E),
+        'y' => Some(B | C | D | F | G),
+        _ => None,
+    }
+}
+
+// Letters that are missing in one case are drawn in the other one.
+fn get_seven_segments(c: char) -> u16 {
+    get_common_segments(c)
+        .or_else(|| lookup_seven_segments(c))
+        .or_else(|| lookup_seven_segments(c.to_ascii_uppercase()))
+        .or_else(|| lookup_seven_segments(c.to_ascii_lowercase()))
+        .unwrap_or(0)
+}
+
+fn get_fourteen_segments(c: char) -> u16 {
+    if let Some(segments) = get_common_segments(c) {
+        return segments;
+    }
+    match c.to_ascii_uppercase() {
+        'A' => A | B | C | E | F | G,
+        'B' => A | B | C | D | I | L | G2,
+        'C' => A | D | E | F,
+        'D' => A | B | C | D | I | L,
+        'E' => A | D | E | F | G1,
+        'F' => A | E | F | G1,
+        'G' => A | C | D | E | F | G2,
+        'H' => B | C | E | F | G,
+        'I' => A | D | I | L,
+        'J' => B | C | D | E,
+        'K' => E | F | G1 | J | M,
+        'L' => D | E | F,
+        'M' => B | C | E | F | H | J,
+        'N' => B | C | E | F | H | M,
+        'O' => A | B | C | D | E | F,
+        'P' => A | B | E | F | G,
+        'Q' => A | B | C | D | E | F | M,
+        'R' => A | B | E | F | G | M,
+        'S' => A | C | D | F | G,
+        'T' => A | I | L,
+        'U' => B | C | D | E | F,
+        'V' => E | F | J | K,
+        'W' => B | C | E | F | K | M,
+        'X' => H | J | K | M,
+        'Y' => H | J | L,
+        'Z' => A | D | J | K,
+        '+' => G | I | L,
+        '*' => G | H | I | J | K | L | M,
+        '/' => J | K,
+        '\\' => H | M,
+        '(' => J | M,
+        ')' => H | K,
+        _ => 0,
+    }
+}
+
+// Line between two points, with the segment thickness.
+struct Stroke {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+    diagonal: bool,
+}
+
+impl Stroke {
+    fn new(p0: (f32, f32), p1: (f32, f32)) -> Stroke {
+        Stroke {
+            x0: p0.0,
+            y0: p0.1,
+            x1: p1.0,
+            y1: p1.1,
+            diagonal: p0.0 != p1.0 && p0.1 != p1.1,
+        }
+    }
+
+    // Returns true if point (x, y) is covered by the segment. Horizontal and
+    // vertical segments have pointed ends, so they fit together at corners.
+    // Diagonals have flat ends and are shortened to keep them apart from the
+    // segments they join.
+    fn contains(&self, x: f32, y: f32, half_thickness: f32, gap: f32) -> bool {
+        let dx = self.x1 - self.x0;
+        let dy = self.y1 - self.y0;
+        let len = (dx * dx + dy * dy).sqrt();
+        let along = ((x - self.x0) * dx + (y - self.y0) * dy) / len;
+        let perp = (((x - self.x0) * dy - (y - self.y0) * dx) / len).abs();
+        if perp > half_thickness {
+            return false;
+        }
+        let margin = if self.diagonal {
+            half_thickness * 2.0 + gap
+        } else {
+            perp + gap
+        };
+        along >= margin && along <= len - margin
+    }
+}
+
+// Returns strokes for |segments| in a digit cell of the given size.
+fn get_strokes(segments: u16, style: &SegmentStyle) -> Vec<Stroke> {
+    let t = style.thickness as f32;
+    let h = t / 2.0;
+    let width = style.digit_size.width;
+    let height = style.digit_size.height;
+
+    // Middle lines are aligned to the pixel grid, so they are not blurred
+    // over an extra row or column.
+    let left = h;
+    let right = width as f32 - h;
+    let center = (width.saturating_sub(style.thickness) / 2) as f32 + h;
+    let top = h;
+    let middle = (height.saturating_sub(style.thickness) / 2) as f32 + h;
+    let bottom = height as f32 - h;
+
+    let mut strokes = Vec::new();
+    let mut add = |mask: u16, p0: (f32, f32), p1: (f32, f32)| {
+        if segments & mask == mask {
+            strokes.push(Stroke::new(p0, p1));
+        }
+    };
+    add(A, (left, top), (right, top));
+    add(B, (right, top), (right, middle));
+    add(C, (right, middle), (right, bottom));
+    add(D, (left, bottom), (right, bottom));
+    add(E, (left, middle), (left, bottom));
+    add(F, (left, top), (left, middle));
+    match style.segment_type {
+        SegmentType::Seven => add(G, (left, middle), (right, middle)),
+        SegmentType::Fourteen => {
+            add(G1, (left, middle), (center, middle));
+            add(G2, (center, middle), (right, middle));
+            add(H, (left, top), (center, middle));
+            add(I, (center, top), (center, middle));
+            add(J, (right, top), (center, middle));
+            add(K, (center, middle), (left, bottom));
+            add(L, (center, middle), (center, bottom));
+            add(M, (center, middle), (right, bottom));
+        }
+    }
+    strokes
+}
+
+enum Cell {
+    Digit(Vec<Stroke>),
+
+    // Squares for '.' and ':', given as their top edges.
+    Dots(Vec<usize>),
+}
+
+impl Cell {
+    fn new(c: char, style: &SegmentStyle) -> Cell {
+        let height = style.digit_size.height;
+        let t = style.thickness;
+        match c {
+            '.' | ',' => Cell::Dots(vec![height.saturating_sub(t)]),
+            ':' => Cell::Dots(vec![
+                (height / 3).saturating_sub(t / 2),
+                (height * 2 / 3).saturating_sub(t / 2),
+            ]),
+            _ => {
+                let segments = match style.segment_type {
+                    SegmentType::Seven => get_seven_segments(c),
+                    SegmentType::Fourteen => get_fourteen_segments(c),
+                };
+                Cell::Digit(get_strokes(segments, style))
+            }
+        }
+    }
+
+    fn width(&self, style: &SegmentStyle) -> usize {
+        match *self {
+            Cell::Digit(_) => style.digit_size.width,
+            Cell::Dots(_) => style.thickness,
+        }
+    }
+
+    fn contains(&self, x: usize, y: usize, style: &SegmentStyle) -> bool {
+        match *self {
+            Cell::Digit(ref strokes) => {
+                let h = style.thickness as f32 / 2.0;
+                let gap = style.gap as f32;
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                strokes.iter().any(|s| s.contains(px, py, h, gap))
+            }
+            Cell::Dots(ref tops) => tops
+                .iter()
+                .any(|&top| y >= top && y < top + style.thickness),
+        }
+    }
+}
+
+// Returns size of |text| drawn with segment digits, including slant.
+pub fn measure_segment_text(text: &str, style: &SegmentStyle) -> Size {
+    if style.digit_size.height == 0 {
+        return Size::zero();
+    }
+
+    let mut width = 0;
+    for (i, c) in text.chars().enumerate() {
+        if i > 0 {
+            width += style.spacing;
+        }
+        width += Cell::new(c, style).width(style);
+    }
+    if width > 0 {
+        width += style.slant.unsigned_abs() as usize;
+    }
+    Size::wh(width, style.digit_size.height)
+}
+
+impl Canvas {
+    // Draws |text| with seven- or fourteen-segment digits. |pos| is the
+    // top-left corner of the text. Besides digits and letters, '.' and ':'
+    // are drawn as narrow dot cells. Characters that can't be displayed are
+    // left blank.
+    pub fn draw_segment_text(
+        &mut self,
+        pos: Vector,
+        text: &str,
+        style: &SegmentStyle,
+        color: Color,
+    ) {
+        let height = style.digit_size.height;
+        if height == 0 {
+            return;
+        }
+
+        let mut x = 0;
+        for c in text.chars() {
+            let cell = Cell::new(c, style);
+            let width = cell.width(style);
+            for y in 0..height {
+                // Digits are slanted by shifting rows. The shift is measured
+                // at the row center, so that it's symmetric.
+                let shift = cmp::max(0, -style.slant) as f32
+                    + style.slant as f32 * (height as f32 - y as f32 - 0.5) / height as f32;
+                let shift = shift.round() as i16;
+                for cx in 0..width {
+                    if cell.contains(cx, y, style) {
+                        let point = pos.add_xy((x + cx) as i16 + shift, y as i16);
+                        self.set_pixel(point, color);
+                    }
+                }
+            }
+            x += width + style.spacing;
+        }
+    }
+
+    pub fn get_segment_text_rect(&self, pos: Vector, text: &str, style: &SegmentStyle) -> Rect {
+        Rect::ps(pos, measure_segment_text(text, style))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gfx::Frame;
+
+    fn count_pixels(canvas: Canvas) -> usize {
+        let frame = canvas.take_frame();
+        let size = frame.size();
+        (0..size.height)
+            .flat_map(|y| (0..size.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| frame.get_pixel(x, y))
+            .count()
+    }
+
+    fn draw(text: &str, style: &SegmentStyle) -> usize {
+        let size = measure_segment_text(text, style);
+        let mut canvas = Canvas::new(Frame::new(size));
+        canvas.draw_segment_text(Vector::zero(), text, style, Color::Light);
+        count_pixels(canvas)
+    }
+
+    #[test]
+    fn printable_ascii() {
+        for &segment_type in [SegmentType::Seven, SegmentType::Fourteen].iter() {
+            let mut style = SegmentStyle::new(segment_type, 16);
+            for c in (0x20u8..0x7f).map(|c| c as char) {
+                draw(&c.to_string(), &style);
+            }
+            style.slant = -3;
+            let text: String = (0x20u8..0x7f).map(|c| c as char).collect();
+            assert!(draw(&text, &style) > 0);
+        }
+    }
+
+    #[test]
+    fn missing_case() {
+        let style = SegmentStyle::new(SegmentType::Seven, 16);
+        assert_eq!(get_seven_segments('a'), get_seven_segments('A'));
+        assert_eq!(get_seven_segments('B'), get_seven_segments('b'));
+        assert_eq!(get_seven_segments('K'), 0);
+        assert_eq!(draw("OK", &style), draw("O", &style));
+    }
+
+    #[test]
+    fn size() {
+        let style = SegmentStyle::new(SegmentType::Seven, 16);
+        let width = 2 * style.digit_size.width + style.thickness + 2 * style.spacing;
+        assert!(measure_segment_text("1.2", &style) == Size::wh(width, 16));
+        assert!(measure_segment_text("", &style) == Size::wh(0, 16));
+    }
+
+    #[test]
+    fn degenerate_styles() {
+        let style = SegmentStyle::new(SegmentType::Seven, 0);
+        assert!(measure_segment_text("1.2", &style) == Size::zero());
+        assert_eq!(draw("1.2", &style), 0);
+
+        let mut style = SegmentStyle::new(SegmentType::Fourteen, 4);
+        style.thickness = 6;
+        draw("1.2:", &style);
+    }
+}
@@ -0,0 +1,250 @@
+extern crate rppal;
+
+use self::rppal::spi;
+use super::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time;
+
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub enum SharpMemoryType {
+    // 1.28", 128x128.
+    Ls013b7dh03,
+
+    // 2.7", 400x240.
+    Ls027b7dh01,
+}
+
+#[derive(Clone, Copy)]
+pub struct SharpMemoryConfig {
+    pub type_: SharpMemoryType,
+    pub bus: spi::Bus,
+    pub slave_select: spi::SlaveSelect,
+    pub clock_speed: u32,
+
+    // The panel requires polarity of the liquid crystal voltage to be
+    // inverted periodically, normally between 1 and 60 times per second.
+    pub vcom_period: time::Duration,
+
+    // By default Light pixels are shown black on white background. Set this
+    // flag to show them white on black.
+    pub invert: bool,
+}
+
+impl SharpMemoryConfig {
+    pub fn new(type_: SharpMemoryType) -> SharpMemoryConfig {
+        SharpMemoryConfig {
+            type_,
+            bus: spi::Bus::Spi0,
+            slave_select: spi::SlaveSelect::Ss0,
+            clock_speed: 1_000_000,
+            vcom_period: time::Duration::from_millis(500),
+            invert: false,
+        }
+    }
+}
+
+const COMMAND_WRITE_LINE: u8 = 0x01;
+const COMMAND_VCOM: u8 = 0x02;
+const COMMAND_CLEAR: u8 = 0x04;
+
+// Maximum size of a single SPI transfer. Lines are split between several
+// write commands when a frame doesn't fit.
+const MAX_TRANSFER_SIZE: usize = 4096;
+
+// State shared with the VCOM thread.
+struct Panel {
+    spi: spi::Spi,
+    vcom: bool,
+}
+
+impl Panel {
+    fn command_byte(&self, cmd: u8) -> u8 {
+        if self.vcom {
+            cmd | COMMAND_VCOM
+        } else {
+            cmd
+        }
+    }
+
+    // The panel expects LSB first, while SPI on Raspberry Pi supports only
+    // MSB first, so all bytes are bit-reversed before they are sent.
+    fn write(&mut self, data: &[u8]) -> Result<()> {
+        let mut data = data.to_vec();
+        spi::reverse_bits(&mut data[..]);
+        assert!(self.spi.write(&data[..])? == data.len());
+        Ok(())
+    }
+
+    fn toggle_vcom(&mut self) -> Result<()> {
+        self.vcom = !self.vcom;
+        let cmd = self.command_byte(0);
+        self.write(&[cmd, 0x00])
+    }
+}
+
+pub struct SharpMemory {
+    config: SharpMemoryConfig,
+    panel: Arc<Mutex<Panel>>,
+    stop_vcom_thread: Arc<AtomicBool>,
+    vcom_thread: Option<thread::JoinHandle<()>>,
+    cur_frame: Option<gfx::Frame>,
+    old_frame: Option<gfx::Frame>,
+}
+
+// Returns a mask of the rows in |page| that are different between the two
+// frames.
+fn changed_rows(a: &gfx::Frame, b: &gfx::Frame, page: usize) -> u8 {
+    let width = a.size().width;
+    let pos = page * width;
+    let a = &a.data()[pos..(pos + width)];
+    let b = &b.data()[pos..(pos + width)];
+    a.iter()
+        .zip(b.iter())
+        .fold(0, |mask, (a, b)| mask | (a ^ b))
+}
+
+impl SharpMemory {
+    pub fn new(config: SharpMemoryConfig) -> Result<SharpMemory> {
+        let spi = spi::Spi::new(
+            config.bus,
+            config.slave_select,
+            config.clock_speed,
+            spi::Mode::Mode0,
+        )?;
+
+        // Chip select is active high on these panels.
+        spi.set_ss_polarity(spi::Polarity::ActiveHigh)?;
+
+        let mut panel = Panel { spi, vcom: false };
+        panel.write(&[COMMAND_CLEAR, 0x00])?;
+
+        let panel = Arc::new(Mutex::new(panel));
+        let stop_vcom_thread = Arc::new(AtomicBool::new(false));
+
+        let vcom_thread = {
+            let panel = panel.clone();
+            let stop = stop_vcom_thread.clone();
+            let period = config.vcom_period;
+            thread::spawn(move || loop {
+                thread::park_timeout(period);
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Err(e) = panel.lock().unwrap().toggle_vcom() {
+                    println!("SharpMemory: Failed to toggle VCOM: {}", e.msg);
+                }
+            })
+        };
+
+        Ok(SharpMemory {
+            config,
+            panel,
+            stop_vcom_thread,
+            vcom_thread: Some(vcom_thread),
+            cur_frame: None,
+            old_frame: None,
+        })
+    }
+
+    // Packs row |y| of |frame| in the format expected by the panel: line
+    // address, followed by the pixels and a trailing dummy byte.
+    fn pack_line(&self, frame: &gfx::Frame, y: usize, out: &mut Vec<u8>) {
+        // Line addresses are 1-based.
+        out.push((y + 1) as u8);
+        let width = frame.size().width;
+        let start = out.len();
+        out.resize(start + width / 8, 0);
+        for x in 0..width {
+            // Set bits are white.
+            if frame.get_pixel(x, y) == self.config.invert {
+                out[start + x / 8] |= 1 << (x % 8);
+            }
+        }
+        out.push(0x00);
+    }
+
+    fn show_frame_internal(&mut self, frame: gfx::Frame) -> Result<()> {
+        assert!(frame.size() == self.size());
+
+        let rows: Vec<usize> = match self.cur_frame {
+            None => (0..frame.size().height).collect(),
+            Some(ref old) => {
+                let mut rows = Vec::new();
+                for page in 0..frame.num_rows() {
+                    let mask = changed_rows(&frame, old, page);
+                    for bit in 0..8 {
+                        if mask & (1 << bit) != 0 {
+                            rows.push(page * 8 + bit);
+                        }
+                    }
+                }
+                rows
+            }
+        };
+
+        let line_size = frame.size().width / 8 + 2;
+        let lines_per_write = (MAX_TRANSFER_SIZE - 2) / line_size;
+        {
+            let mut panel = self.panel.lock().unwrap();
+            for chunk in rows.chunks(lines_per_write) {
+                let mut data = Vec::with_capacity(chunk.len() * line_size + 2);
+                data.push(panel.command_byte(COMMAND_WRITE_LINE));
+                for &y in chunk {
+                    self.pack_line(&frame, y, &mut data);
+                }
+                data.push(0x00);
+                panel.write(&data[..])?;
+            }
+        }
+
+        if let Some(old) = self.cur_frame.take() {
+            self.old_frame = Some(old);
+        }
+        self.cur_frame = Some(frame);
+
+        Ok(())
+    }
+}
+
+impl Drop for SharpMemory {
+    fn drop(&mut self) {
+        self.stop_vcom_thread.store(true, Ordering::SeqCst);
+        if let Some(t) = self.vcom_thread.take() {
+            t.thread().unpark();
+            let _ = t.join();
+        }
+    }
+}
+
+impl DisplayDriver for SharpMemory {
+    fn size(&self) -> gfx::Size {
+        match self.config.type_ {
+            SharpMemoryType::Ls013b7dh03 => gfx::Size {
+                width: 128,
+                height: 128,
+            },
+            SharpMemoryType::Ls027b7dh01 => gfx::Size {
+                width: 400,
+                height: 240,
+            },
+        }
+    }
+
+    fn show_frame(&mut self, frame: gfx::Frame) {
+        if let Err(e) = self.show_frame_internal(frame) {
+            println!("SharpMemory: Failed to push a frame: {}", e.msg);
+        }
+    }
+
+    fn get_frame(&mut self) -> gfx::Frame {
+        match self.old_frame.take() {
+            Some(mut f) => {
+                f.clear();
+                f
+            }
+            None => gfx::Frame::new(self.size()),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+extern crate rppal;
+
+use self::rppal::gpio;
+use self::rppal::spi;
+use super::*;
+use std::thread;
+use std::time;
+
+pub use self::rppal::spi::{Bus, SlaveSelect};
+
+impl From<spi::Error> for Error {
+    fn from(e: spi::Error) -> Error {
+        Error::from_string(format!("SPI error: {}", e))
+    }
+}
+
+impl From<gpio::Error> for Error {
+    fn from(e: gpio::Error) -> Error {
+        Error::from_string(format!("GPIO error: {}", e))
+    }
+}
+
+// spidev rejects transfers larger than its buffer size (4096 bytes by
+// default), so longer writes are split into chunks.
+const MAX_TRANSFER_SIZE: usize = 4096;
+
+// Wiring of a display controller that is connected to SPI and uses a
+// separate Data/Command GPIO pin.
+#[derive(Clone, Copy)]
+pub struct SpiConfig {
+    pub bus: Bus,
+    pub slave_select: SlaveSelect,
+    pub clock_speed: u32,
+    pub dc_pin: u8,
+    pub reset_pin: Option<u8>,
+}
+
+impl SpiConfig {
+    pub fn new(dc_pin: u8, reset_pin: Option<u8>) -> SpiConfig {
+        SpiConfig {
+            bus: Bus::Spi0,
+            slave_select: SlaveSelect::Ss0,
+            clock_speed: 8_000_000,
+            dc_pin,
+            reset_pin,
+        }
+    }
+}
+
+pub struct SpiBus {
+    spi: spi::Spi,
+    gpio: gpio::Gpio,
+    dc_pin: u8,
+    reset_pin: Option<u8>,
+}
+
+impl SpiBus {
+    pub fn new(config: &SpiConfig) -> Result<SpiBus> {
+        let spi = spi::Spi::new(
+            config.bus,
+            config.slave_select,
+            config.clock_speed,
+            spi::Mode::Mode0,
+        )?;
+        let mut gpio = gpio::Gpio::new()?;
+        gpio.set_mode(config.dc_pin, gpio::Mode::Output);
+        if let Some(pin) = config.reset_pin {
+            gpio.set_mode(pin, gpio::Mode::Output);
+            gpio.write(pin, gpio::Level::High);
+        }
+        Ok(SpiBus {
+            spi,
+            gpio,
+            dc_pin: config.dc_pin,
+            reset_pin: config.reset_pin,
+        })
+    }
+
+    pub fn gpio(&mut self) -> &mut gpio::Gpio {
+        &mut self.gpio
+    }
+
+    // Pulses the RESET pin, if there is one.
+    pub fn hardware_reset(&mut self) {
+        if let Some(pin) = self.reset_pin {
+            self.gpio.write(pin, gpio::Level::Low);
+            thread::sleep(time::Duration::from_millis(10));
+            self.gpio.write(pin, gpio::Level::High);
+            thread::sleep(time::Duration::from_millis(10));
+        }
+    }
+
+    fn write(&mut self, level: gpio::Level, content: &[u8]) -> Result<()> {
+        self.gpio.write(self.dc_pin, level);
+        for chunk in content.chunks(MAX_TRANSFER_SIZE) {
+            assert!(self.spi.write(chunk)? == chunk.len());
+        }
+        Ok(())
+    }
+
+    pub fn send_command(&mut self, cmd: &[u8]) -> Result<()> {
+        self.write(gpio::Level::Low, cmd)
+    }
+
+    pub fn send_data(&mut self, data: &[u8]) -> Result<()> {
+        self.write(gpio::Level::High, data)
+    }
+}
@@ -2,6 +2,7 @@ extern crate rppal;
 
 use self::rppal::i2c;
 use super::*;
+use driver::find_span_end;
 
 impl From<i2c::Error> for Error {
     fn from(e: i2c::Error) -> Error {
@@ -33,23 +34,6 @@ pub struct Ssd1306 {
 const COMMAND_DISPLAY_OFF: u8 = 0xAE;
 const COMMAND_DISPLAY_ON: u8 = 0xAF;
 
-// Helper for show_frame_diff() used to find spans that a different
-// between |a| and |b|. Two spans that are less than 5 pixels apart are
-// considered part of the same span to account for the costs of sending
-// page address.
-fn find_span_end(a: &[u8], b: &[u8]) -> usize {
-    let mut last_diff = 0;
-    for i in 1..a.len() {
-        if a[i] != b[i] {
-            last_diff = i;
-        }
-        if i - last_diff > 4 {
-            return last_diff + 1;
-        }
-    }
-    last_diff + 1
-}
-
 impl Ssd1306 {
     pub fn new(type_: Ssd1306Type, flip: bool) -> Result<Ssd1306> {
         let mut bus = i2c::I2c::new()?;
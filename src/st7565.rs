@@ -0,0 +1,250 @@
+use super::*;
+use driver::find_span_end;
+use spi_bus::SpiBus;
+use std::thread;
+use std::time;
+
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub enum St7565Type {
+    St7565,
+    St7567,
+    Uc1701,
+}
+
+// LCD bias ratio. The right value depends on the glass and is normally
+// specified in the panel datasheet.
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub enum Bias {
+    OneNinth,
+    OneSeventh,
+}
+
+#[derive(Clone, Copy)]
+pub struct St7565Config {
+    pub type_: St7565Type,
+    pub spi: SpiConfig,
+    pub size: gfx::Size,
+    pub bias: Bias,
+
+    // Internal regulator resistor ratio, 0-7.
+    pub regulator_ratio: u8,
+
+    // Electronic volume (contrast), 0-63.
+    pub electronic_volume: u8,
+
+    // First RAM column that is connected to the glass. The controllers have
+    // 132 columns of display RAM and many 128 pixel wide panels don't start
+    // at column 0, particularly when the segment direction is reversed.
+    pub column_offset: u8,
+
+    pub flip: bool,
+}
+
+impl St7565Config {
+    pub fn new(type_: St7565Type, spi: SpiConfig) -> St7565Config {
+        let (bias, regulator_ratio, electronic_volume) = match type_ {
+            St7565Type::St7565 => (Bias::OneSeventh, 6, 0x18),
+            St7565Type::St7567 => (Bias::OneNinth, 5, 0x20),
+            St7565Type::Uc1701 => (Bias::OneNinth, 3, 0x1F),
+        };
+        St7565Config {
+            type_,
+            spi,
+            size: gfx::Size::wh(128, 64),
+            bias,
+            regulator_ratio,
+            electronic_volume,
+            column_offset: 0,
+            flip: false,
+        }
+    }
+}
+
+pub struct St7565 {
+    config: St7565Config,
+    bus: SpiBus,
+    cur_frame: Option<gfx::Frame>,
+    old_frame: Option<gfx::Frame>,
+}
+
+const COMMAND_DISPLAY_OFF: u8 = 0xAE;
+const COMMAND_DISPLAY_ON: u8 = 0xAF;
+
+impl St7565 {
+    pub fn new(config: St7565Config) -> Result<St7565> {
+        if config.regulator_ratio > 7 {
+            return Err(Error::new("Regulator ratio must be in 0-7 range."));
+        }
+        if config.electronic_volume > 63 {
+            return Err(Error::new("Electronic volume must be in 0-63 range."));
+        }
+        if config.size.width + config.column_offset as usize > 132 {
+            return Err(Error::new("Invalid display size."));
+        }
+
+        let bus = SpiBus::new(&config.spi)?;
+        let mut result = St7565 {
+            config,
+            bus,
+            cur_frame: None,
+            old_frame: None,
+        };
+        result.initialize()?;
+
+        Ok(result)
+    }
+
+    fn initialize(&mut self) -> Result<()> {
+        self.bus.hardware_reset();
+
+        // Software reset.
+        self.bus.send_command(&[0xE2])?;
+        thread::sleep(time::Duration::from_millis(10));
+
+        self.bus.send_command(&[COMMAND_DISPLAY_OFF])?;
+
+        // Set LCD bias.
+        let bias = match self.config.bias {
+            Bias::OneNinth => 0xA2,
+            Bias::OneSeventh => 0xA3,
+        };
+        self.bus.send_command(&[bias])?;
+
+        // Set segment direction (ADC select).
+        let flip = self.config.flip;
+        self.bus.send_command(&[if flip { 0xA1 } else { 0xA0 }])?;
+        // Set COM output scan direction.
+        self.bus.send_command(&[if flip { 0xC8 } else { 0xC0 }])?;
+
+        // Set start line to 0.
+        self.bus.send_command(&[0x40])?;
+
+        // Turn on booster, regulator and follower. ST7565 needs the power
+        // circuits to be enabled one at a time.
+        if self.config.type_ == St7565Type::St7565 {
+            for &cmd in [0x2C, 0x2E, 0x2F].iter() {
+                self.bus.send_command(&[cmd])?;
+                thread::sleep(time::Duration::from_millis(50));
+            }
+        } else {
+            self.bus.send_command(&[0x2F])?;
+
+            // Booster ratio 4x.
+            self.bus.send_command(&[0xF8, 0x00])?;
+        }
+
+        // Regulator resistor ratio.
+        self.bus
+            .send_command(&[0x20 | self.config.regulator_ratio])?;
+
+        // Electronic volume.
+        let volume = self.config.electronic_volume;
+        self.set_electronic_volume(volume)?;
+
+        if self.config.type_ == St7565Type::Uc1701 {
+            // Advanced program control: enable temperature compensation.
+            self.bus.send_command(&[0xFA, 0x90])?;
+        }
+
+        // Entire Display ON.
+        self.bus.send_command(&[0xA4])?;
+
+        // Set Normal Display.
+        self.bus.send_command(&[0xA6])?;
+
+        self.bus.send_command(&[COMMAND_DISPLAY_ON])?;
+
+        Ok(())
+    }
+
+    pub fn set_electronic_volume(&mut self, volume: u8) -> Result<()> {
+        if volume > 63 {
+            return Err(Error::new("Electronic volume must be in 0-63 range."));
+        }
+        self.config.electronic_volume = volume;
+        self.bus.send_command(&[0x81, volume])
+    }
+
+    fn set_address(&mut self, page: u8, column: usize) -> Result<()> {
+        let column = column as u8 + self.config.column_offset;
+        self.bus
+            .send_command(&[0xB0 | page, 0x10 | ((column & 0xf0) >> 4), column & 0x0f])
+    }
+
+    fn show_frame_whole(&mut self, frame: &gfx::Frame) -> Result<()> {
+        let width = frame.size().width;
+        for page in 0..frame.num_rows() {
+            self.set_address(page as u8, 0)?;
+            let data_pos = page * width;
+            self.bus
+                .send_data(&frame.data()[data_pos..(data_pos + width)])?;
+        }
+
+        Ok(())
+    }
+
+    fn show_frame_diff(&mut self, frame: &gfx::Frame, old_frame: &gfx::Frame) -> Result<()> {
+        let width = frame.size().width;
+
+        for page in 0..frame.num_rows() {
+            let data_pos = page * width;
+            let old = &old_frame.data()[data_pos..(data_pos + width)];
+            let new = &frame.data()[data_pos..(data_pos + width)];
+            let mut pos = 0;
+            while pos < width {
+                if old[pos] == new[pos] {
+                    pos += 1;
+                    continue;
+                }
+
+                let end = pos + find_span_end(&old[pos..], &new[pos..]);
+
+                self.set_address(page as u8, pos)?;
+                self.bus.send_data(&new[pos..end])?;
+
+                pos = end;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn show_frame_internal(&mut self, frame: gfx::Frame) -> Result<()> {
+        assert!(frame.size() == self.config.size);
+
+        match self.cur_frame.take() {
+            None => {
+                self.show_frame_whole(&frame)?;
+            }
+            Some(old) => {
+                self.show_frame_diff(&frame, &old)?;
+                self.old_frame = Some(old);
+            }
+        };
+        self.cur_frame = Some(frame);
+
+        Ok(())
+    }
+}
+
+impl DisplayDriver for St7565 {
+    fn size(&self) -> gfx::Size {
+        self.config.size
+    }
+
+    fn show_frame(&mut self, frame: gfx::Frame) {
+        if let Err(e) = self.show_frame_internal(frame) {
+            println!("St7565: Failed to push a frame: {}", e.msg);
+        }
+    }
+
+    fn get_frame(&mut self) -> gfx::Frame {
+        match self.old_frame.take() {
+            Some(mut f) => {
+                f.clear();
+                f
+            }
+            None => gfx::Frame::new(self.size()),
+        }
+    }
+}
@@ -0,0 +1,418 @@
+use gfx::{extend_rect, Canvas, Color, Font, FontMetrics, Rect, Size, TextStyle, Vector};
+use std::cmp;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum HAlign {
+    Left,
+    Center,
+    Right,
+
+    // Words are spread to fill the whole width. The last line of each
+    // paragraph is aligned to the left.
+    Justify,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum VAlign {
+    Top,
+    Middle,
+    Bottom,
+}
+
+// Part of the text that is replaced with ellipsis when it's too long.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Truncation {
+    Start,
+    Middle,
+    End,
+}
+
+#[derive(Clone, Copy)]
+pub struct LayoutOptions {
+    pub h_align: HAlign,
+    pub v_align: VAlign,
+
+    // When set, words that are wider than the layout rect are broken with a
+    // hyphen. Otherwise they are broken between characters.
+    pub hyphenate: bool,
+
+    // Extra space between lines, in pixels.
+    pub line_spacing: i16,
+
+    // When set and the text doesn't fit vertically, the last visible line
+    // ends with ellipsis.
+    pub ellipsis: bool,
+}
+
+impl LayoutOptions {
+    pub fn new(h_align: HAlign, v_align: VAlign) -> LayoutOptions {
+        LayoutOptions {
+            h_align,
+            v_align,
+            hyphenate: true,
+            line_spacing: 0,
+            ellipsis: false,
+        }
+    }
+}
+
+// Piece of a single-line rich text. Spans are drawn one after another on a
+// common baseline, each with its own font, style and color.
+#[derive(Clone, Copy)]
+pub struct TextSpan<'a> {
+    pub text: &'a str,
+    pub font: &'a Font,
+    pub style: TextStyle,
+    pub color: Color,
+}
+
+impl<'a> TextSpan<'a> {
+    pub fn new(text: &'a str, font: &'a Font) -> TextSpan<'a> {
+        TextSpan {
+            text,
+            font,
+            style: TextStyle::default(),
+            color: Color::Light,
+        }
+    }
+}
+
+struct LayoutLine {
+    baseline: i16,
+
+    // Pieces of text in the line and their horizontal offsets. Justified
+    // lines are split into words, other lines have a single fragment.
+    fragments: Vec<(i16, String)>,
+}
+
+// Result of laying out a multi-line text in a rectangle: text is split into
+// paragraphs on '\n' and each paragraph is wrapped at word boundaries.
+pub struct TextLayout<'a> {
+    font: &'a Font,
+    rect: Rect,
+    lines: Vec<LayoutLine>,
+    overflow: bool,
+}
+
+fn get_ellipsis(font: &Font) -> &'static str {
+    if font.has_glyph('\u{2026}') {
+        "\u{2026}"
+    } else {
+        "..."
+    }
+}
+
+// Returns the longest truncated version of |text| that fits in |max_width|
+// with ellipsis. If |force_ellipsis| is false then |text| is returned as is
+// when it fits.
+fn truncate(
+    font: &Font,
+    text: &str,
+    max_width: i16,
+    truncation: Truncation,
+    force_ellipsis: bool,
+) -> String {
+    if !force_ellipsis && font.measure(text) <= max_width {
+        return text.to_string();
+    }
+
+    let ellipsis = get_ellipsis(font);
+    let chars: Vec<char> = text.chars().collect();
+
+    // Returns text with |n| characters kept.
+    let shorten = |n: usize| -> String {
+        let (head, tail) = match truncation {
+            Truncation::Start => (0, n),
+            Truncation::Middle => ((n + 1) / 2, n / 2),
+            Truncation::End => (n, 0),
+        };
+        let head: String = chars[..head].iter().collect();
+        let tail: String = chars[(chars.len() - tail)..].iter().collect();
+        format!("{}{}{}", head.trim_end(), ellipsis, tail.trim_start())
+    };
+
+    let mut n = if force_ellipsis {
+        chars.len()
+    } else {
+        chars.len().saturating_sub(1)
+    };
+    loop {
+        let result = shorten(n);
+        if font.measure(&result) <= max_width {
+            return result;
+        }
+        if n == 0 {
+            return String::new();
+        }
+        n -= 1;
+    }
+}
+
+// Truncates |text| to fit in |max_width|, replacing the removed part with
+// ellipsis. Text that fits is returned unchanged.
+pub fn truncate_text(font: &Font, text: &str, max_width: usize, truncation: Truncation) -> String {
+    let max_width = cmp::min(max_width, i16::MAX as usize) as i16;
+    truncate(font, text, max_width, truncation, false)
+}
+
+fn get_align_offset(h_align: HAlign, free: i16) -> i16 {
+    match h_align {
+        HAlign::Left | HAlign::Justify => 0,
+        HAlign::Center => free / 2,
+        HAlign::Right => free,
+    }
+}
+
+// Splits |word| that doesn't fit in |max_width| into a prefix that fits and
+// the rest.
+fn break_word(font: &Font, word: &str, max_width: i16, hyphenate: bool) -> (String, String) {
+    let chars: Vec<char> = word.chars().collect();
+
+    if hyphenate {
+        // Leave at least two characters on each side of the hyphen.
+        for n in (2..chars.len().saturating_sub(1)).rev() {
+            let mut prefix: String = chars[..n].iter().collect();
+            prefix.push('-');
+            if font.measure(&prefix) <= max_width {
+                return (prefix, chars[n..].iter().collect());
+            }
+        }
+    }
+
+    // Always take at least one character to make progress, even when it's
+    // wider than the rect.
+    let mut n = 1;
+    while n < chars.len() {
+        let prefix: String = chars[..(n + 1)].iter().collect();
+        if font.measure(&prefix) > max_width {
+            break;
+        }
+        n += 1;
+    }
+    (chars[..n].iter().collect(), chars[n..].iter().collect())
+}
+
+// Wraps a paragraph into lines. Each line is returned as a list of words.
+fn wrap_paragraph(
+    font: &Font,
+    paragraph: &str,
+    max_width: i16,
+    hyphenate: bool,
+) -> Vec<Vec<String>> {
+    let space_width = font.measure(" ");
+    let mut lines = Vec::new();
+    let mut line: Vec<String> = Vec::new();
+    let mut line_width = 0;
+
+    for word in paragraph.split(' ').filter(|w| !w.is_empty()) {
+        let mut word = word.to_string();
+        loop {
+            let width = font.measure(&word);
+            if !line.is_empty() && line_width + space_width + width <= max_width {
+                line_width += space_width + width;
+                line.push(word);
+                break;
+            }
+            if !line.is_empty() {
+                lines.push(line);
+                line = Vec::new();
+            }
+            if width <= max_width {
+                line_width = width;
+                line.push(word);
+                break;
+            }
+            let (head, tail) = break_word(font, &word, max_width, hyphenate);
+            lines.push(vec![head]);
+            word = tail;
+        }
+    }
+
+    // Empty paragraphs still take a line.
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+impl<'a> TextLayout<'a> {
+    pub fn new(font: &'a Font, text: &str, rect: Rect, options: LayoutOptions) -> TextLayout<'a> {
+        let max_width = rect.size.width as i16;
+
+        // Wrap all lines and align them horizontally.
+        let mut lines = Vec::new();
+        let mut overflow = false;
+        for paragraph in text.split('\n') {
+            let wrapped = wrap_paragraph(font, paragraph, max_width, options.hyphenate);
+            let num_lines = wrapped.len();
+            for (i, words) in wrapped.into_iter().enumerate() {
+                let text = words.join(" ");
+                let width = font.measure(&text);
+                if width > max_width {
+                    overflow = true;
+                }
+                let free = cmp::max(0, max_width - width);
+                let last_in_paragraph = i + 1 == num_lines;
+                let fragments = match options.h_align {
+                    HAlign::Justify if !last_in_paragraph && words.len() > 1 => {
+                        let gaps = words.len() as i16 - 1;
+                        let space_width = font.measure(" ");
+                        let mut x = 0;
+                        let mut fragments = Vec::with_capacity(words.len());
+                        for (n, word) in words.into_iter().enumerate() {
+                            let n = n as i16;
+                            if n > 0 {
+                                x += space_width + free / gaps;
+                                if n <= free % gaps {
+                                    x += 1;
+                                }
+                            }
+                            let word_width = font.measure(&word);
+                            fragments.push((x, word));
+                            x += word_width;
+                        }
+                        fragments
+                    }
+                    _ => vec![(get_align_offset(options.h_align, free), text)],
+                };
+                lines.push(LayoutLine {
+                    baseline: 0,
+                    fragments,
+                });
+            }
+        }
+
+        // Drop lines that don't fit vertically.
+        let metrics = font.metrics();
+        let line_height = metrics.line_height + options.line_spacing;
+        let max_height = rect.size.height as i16;
+        let mut num_lines = 0;
+        let text_height = |n: i16| {
+            if n == 0 {
+                0
+            } else {
+                (n - 1) * line_height + metrics.ascent + metrics.descent
+            }
+        };
+        while num_lines < lines.len() && text_height(num_lines as i16 + 1) <= max_height {
+            num_lines += 1;
+        }
+        if num_lines < lines.len() {
+            overflow = true;
+            lines.truncate(num_lines);
+
+            if options.ellipsis {
+                if let Some(line) = lines.last_mut() {
+                    let words: Vec<&str> = line.fragments.iter().map(|f| f.1.as_str()).collect();
+                    let text = truncate(font, &words.join(" "), max_width, Truncation::End, true);
+                    let free = cmp::max(0, max_width - font.measure(&text));
+                    line.fragments = vec![(get_align_offset(options.h_align, free), text)];
+                }
+            }
+        }
+
+        // Align vertically.
+        let free = cmp::max(0, max_height - text_height(num_lines as i16));
+        let top = rect.top()
+            + match options.v_align {
+                VAlign::Top => 0,
+                VAlign::Middle => free / 2,
+                VAlign::Bottom => free,
+            };
+        for (i, line) in lines.iter_mut().enumerate() {
+            line.baseline = top + metrics.ascent + i as i16 * line_height;
+        }
+
+        TextLayout {
+            font,
+            rect,
+            lines,
+            overflow,
+        }
+    }
+
+    // Returns true if the text didn't fit in the layout rect. Lines that
+    // didn't fit are not drawn.
+    pub fn overflow(&self) -> bool {
+        self.overflow
+    }
+
+    pub fn num_lines(&self) -> usize {
+        self.lines.len()
+    }
+}
+
+// Returns the width of |spans| drawn one after another.
+pub fn measure_spans(spans: &[TextSpan]) -> i16 {
+    spans.iter().map(|s| s.font.measure(s.text)).sum()
+}
+
+// Returns metrics for a line made of |spans|: the largest ascent, descent,
+// line height and pixel size of the fonts used in the spans.
+pub fn get_spans_metrics(spans: &[TextSpan]) -> FontMetrics {
+    let mut result = FontMetrics {
+        ascent: 0,
+        descent: 0,
+        line_height: 0,
+        pixel_size: 0,
+    };
+    for span in spans {
+        let metrics = span.font.metrics();
+        result.ascent = cmp::max(result.ascent, metrics.ascent);
+        result.descent = cmp::max(result.descent, metrics.descent);
+        result.line_height = cmp::max(result.line_height, metrics.line_height);
+        result.pixel_size = cmp::max(result.pixel_size, metrics.pixel_size);
+    }
+    result
+}
+
+impl Canvas {
+    // Draws |spans| one after another starting at |pos|, which is on the
+    // baseline. Returns pen position after the last span.
+    pub fn draw_spans(&mut self, pos: Vector, spans: &[TextSpan]) -> Vector {
+        let mut pos = pos;
+        for span in spans {
+            self.draw_styled_text(pos, span.font, span.text, span.style, span.color);
+            pos.x += span.font.measure(span.text);
+        }
+        pos
+    }
+
+    pub fn get_spans_rect(&self, pos: Vector, spans: &[TextSpan]) -> Rect {
+        let mut pos = pos;
+        let mut result: Option<Rect> = None;
+        for span in spans {
+            let rect = self.get_styled_text_rect(pos, span.font, span.text, span.style);
+            match result {
+                None => result = Some(rect),
+                Some(ref mut r) => extend_rect(r, rect),
+            }
+            pos.x += span.font.measure(span.text);
+        }
+        result.unwrap_or_else(|| Rect::ps(pos, Size::zero()))
+    }
+
+    // Draws |text| truncated to fit in |max_width|.
+    pub fn draw_text_clipped(
+        &mut self,
+        pos: Vector,
+        font: &Font,
+        text: &str,
+        max_width: usize,
+        truncation: Truncation,
+        color: Color,
+    ) {
+        let text = truncate_text(font, text, max_width, truncation);
+        self.draw_text(pos, font, &text, color);
+    }
+
+    pub fn draw_text_layout(&mut self, layout: &TextLayout, color: Color) {
+        for line in layout.lines.iter() {
+            for &(x, ref text) in line.fragments.iter() {
+                let pos = Vector::xy(layout.rect.left() + x, line.baseline);
+                self.draw_text(pos, layout.font, text, color);
+            }
+        }
+    }
+}
@@ -0,0 +1,344 @@
+use super::*;
+use spi_bus::SpiBus;
+use std::cmp;
+use std::thread;
+use std::time;
+
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub enum TftType {
+    St7735,
+    St7789,
+    Ili9341,
+}
+
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub struct RgbColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl RgbColor {
+    pub fn rgb(r: u8, g: u8, b: u8) -> RgbColor {
+        RgbColor { r, g, b }
+    }
+
+    fn to_rgb565(self) -> [u8; 2] {
+        let v =
+            ((self.r as u16 & 0xF8) << 8) | ((self.g as u16 & 0xFC) << 3) | (self.b as u16 >> 3);
+        [(v >> 8) as u8, v as u8]
+    }
+}
+
+// Colors used to show Light and Dark pixels.
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub struct Palette {
+    pub light: RgbColor,
+    pub dark: RgbColor,
+}
+
+impl Palette {
+    pub fn white_on_black() -> Palette {
+        Palette {
+            light: RgbColor::rgb(0xff, 0xff, 0xff),
+            dark: RgbColor::rgb(0x00, 0x00, 0x00),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct TftConfig {
+    pub type_: TftType,
+    pub spi: SpiConfig,
+
+    // Size of the panel in pixels, after rotation.
+    pub panel_size: gfx::Size,
+
+    // Offset of the panel in the controller RAM. Many small ST7735 and
+    // ST7789 panels don't start at (0, 0).
+    pub offset: gfx::Vector,
+
+    pub rotation: gfx::Rotation,
+
+    // Set for panels with BGR subpixel order.
+    pub bgr: bool,
+
+    // Some panels, most ST7789 ones, need colors to be inverted.
+    pub invert_colors: bool,
+
+    // Each frame pixel is shown as scale x scale block of panel pixels.
+    pub scale: usize,
+
+    pub palette: Palette,
+}
+
+impl TftConfig {
+    pub fn new(type_: TftType, spi: SpiConfig, panel_size: gfx::Size) -> TftConfig {
+        TftConfig {
+            type_,
+            spi,
+            panel_size,
+            offset: gfx::Vector::zero(),
+            rotation: gfx::Rotation::Rotate0,
+            bgr: type_ != TftType::St7789,
+            invert_colors: type_ == TftType::St7789,
+            scale: 1,
+            palette: Palette::white_on_black(),
+        }
+    }
+}
+
+pub struct Tft {
+    config: TftConfig,
+    bus: SpiBus,
+    cur_frame: Option<gfx::Frame>,
+    old_frame: Option<gfx::Frame>,
+}
+
+const COMMAND_SOFTWARE_RESET: u8 = 0x01;
+const COMMAND_SLEEP_OUT: u8 = 0x11;
+const COMMAND_NORMAL_MODE: u8 = 0x13;
+const COMMAND_INVERSION_OFF: u8 = 0x20;
+const COMMAND_INVERSION_ON: u8 = 0x21;
+const COMMAND_DISPLAY_ON: u8 = 0x29;
+const COMMAND_COLUMN_ADDRESS: u8 = 0x2A;
+const COMMAND_ROW_ADDRESS: u8 = 0x2B;
+const COMMAND_MEMORY_WRITE: u8 = 0x2C;
+const COMMAND_MEMORY_ACCESS: u8 = 0x36;
+const COMMAND_PIXEL_FORMAT: u8 = 0x3A;
+
+const MADCTL_MY: u8 = 0x80;
+const MADCTL_MX: u8 = 0x40;
+const MADCTL_MV: u8 = 0x20;
+const MADCTL_BGR: u8 = 0x08;
+
+// Returns MADCTL value for the specified rotation. The controllers are
+// normally mounted differently, so the same rotation needs different
+// scan directions.
+fn get_memory_access(type_: TftType, rotation: gfx::Rotation) -> u8 {
+    match (type_, rotation) {
+        (TftType::St7735, gfx::Rotation::Rotate0) => MADCTL_MX | MADCTL_MY,
+        (TftType::St7735, gfx::Rotation::Rotate90) => MADCTL_MY | MADCTL_MV,
+        (TftType::St7735, gfx::Rotation::Rotate180) => 0,
+        (TftType::St7735, gfx::Rotation::Rotate270) => MADCTL_MX | MADCTL_MV,
+        (TftType::St7789, gfx::Rotation::Rotate0) => 0,
+        (TftType::St7789, gfx::Rotation::Rotate90) => MADCTL_MX | MADCTL_MV,
+        (TftType::St7789, gfx::Rotation::Rotate180) => MADCTL_MX | MADCTL_MY,
+        (TftType::St7789, gfx::Rotation::Rotate270) => MADCTL_MY | MADCTL_MV,
+        (TftType::Ili9341, gfx::Rotation::Rotate0) => MADCTL_MX,
+        (TftType::Ili9341, gfx::Rotation::Rotate90) => MADCTL_MV,
+        (TftType::Ili9341, gfx::Rotation::Rotate180) => MADCTL_MY,
+        (TftType::Ili9341, gfx::Rotation::Rotate270) => MADCTL_MX | MADCTL_MY | MADCTL_MV,
+    }
+}
+
+// Returns the smallest rectangle that contains all pixels that are
+// different between |a| and |b|.
+fn get_changed_rect(a: &gfx::Frame, b: &gfx::Frame) -> gfx::Rect {
+    let width = a.size().width;
+    let mut left = width;
+    let mut right = 0;
+    let mut top = a.size().height;
+    let mut bottom = 0;
+    for page in 0..a.num_rows() {
+        let pos = page * width;
+        let a = &a.data()[pos..(pos + width)];
+        let b = &b.data()[pos..(pos + width)];
+        let mut rows = 0u8;
+        for x in 0..width {
+            let diff = a[x] ^ b[x];
+            if diff != 0 {
+                left = cmp::min(left, x);
+                right = cmp::max(right, x + 1);
+                rows |= diff;
+            }
+        }
+        if rows != 0 {
+            top = cmp::min(top, page * 8 + rows.trailing_zeros() as usize);
+            bottom = cmp::max(bottom, page * 8 + 8 - rows.leading_zeros() as usize);
+        }
+    }
+    if left >= right {
+        gfx::Rect::zero()
+    } else {
+        gfx::Rect::ltrb(left as i16, top as i16, right as i16, bottom as i16)
+    }
+}
+
+impl Tft {
+    pub fn new(config: TftConfig) -> Result<Tft> {
+        if config.scale == 0
+            || config.panel_size.width % config.scale != 0
+            || config.panel_size.height % config.scale != 0
+        {
+            return Err(Error::new("Panel size must be a multiple of scale."));
+        }
+
+        let bus = SpiBus::new(&config.spi)?;
+        let mut result = Tft {
+            config,
+            bus,
+            cur_frame: None,
+            old_frame: None,
+        };
+        result.initialize()?;
+
+        Ok(result)
+    }
+
+    fn command(&mut self, cmd: u8, data: &[u8]) -> Result<()> {
+        self.bus.send_command(&[cmd])?;
+        if !data.is_empty() {
+            self.bus.send_data(data)?;
+        }
+        Ok(())
+    }
+
+    fn initialize(&mut self) -> Result<()> {
+        self.bus.hardware_reset();
+
+        self.command(COMMAND_SOFTWARE_RESET, &[])?;
+        thread::sleep(time::Duration::from_millis(150));
+
+        self.command(COMMAND_SLEEP_OUT, &[])?;
+        thread::sleep(time::Duration::from_millis(120));
+
+        match self.config.type_ {
+            TftType::St7735 => {
+                // Frame rate control.
+                self.command(0xB1, &[0x01, 0x2C, 0x2D])?;
+                // Power control.
+                self.command(0xC0, &[0xA2, 0x02, 0x84])?;
+                self.command(0xC1, &[0xC5])?;
+                // VCOM.
+                self.command(0xC5, &[0x0E])?;
+            }
+            TftType::St7789 => (),
+            TftType::Ili9341 => {
+                // Power control.
+                self.command(0xC0, &[0x23])?;
+                self.command(0xC1, &[0x10])?;
+                // VCOM.
+                self.command(0xC5, &[0x3E, 0x28])?;
+                self.command(0xC7, &[0x86])?;
+            }
+        }
+
+        // 16 bits per pixel.
+        self.command(COMMAND_PIXEL_FORMAT, &[0x55])?;
+
+        let mut memory_access = get_memory_access(self.config.type_, self.config.rotation);
+        if self.config.bgr {
+            memory_access |= MADCTL_BGR;
+        }
+        self.command(COMMAND_MEMORY_ACCESS, &[memory_access])?;
+
+        let inversion = if self.config.invert_colors {
+            COMMAND_INVERSION_ON
+        } else {
+            COMMAND_INVERSION_OFF
+        };
+        self.command(inversion, &[])?;
+
+        self.command(COMMAND_NORMAL_MODE, &[])?;
+        self.command(COMMAND_DISPLAY_ON, &[])?;
+
+        Ok(())
+    }
+
+    fn set_window(&mut self, rect: gfx::Rect) -> Result<()> {
+        let x0 = (rect.left() + self.config.offset.x) as u16;
+        let x1 = (rect.right() + self.config.offset.x - 1) as u16;
+        let y0 = (rect.top() + self.config.offset.y) as u16;
+        let y1 = (rect.bottom() + self.config.offset.y - 1) as u16;
+        self.command(
+            COMMAND_COLUMN_ADDRESS,
+            &[(x0 >> 8) as u8, x0 as u8, (x1 >> 8) as u8, x1 as u8],
+        )?;
+        self.command(
+            COMMAND_ROW_ADDRESS,
+            &[(y0 >> 8) as u8, y0 as u8, (y1 >> 8) as u8, y1 as u8],
+        )
+    }
+
+    // Sends pixels in |rect|, specified in frame coordinates.
+    fn send_rect(&mut self, frame: &gfx::Frame, rect: gfx::Rect) -> Result<()> {
+        let scale = self.config.scale;
+        let light = self.config.palette.light.to_rgb565();
+        let dark = self.config.palette.dark.to_rgb565();
+
+        let mut data = Vec::with_capacity(rect.size.width * rect.size.height * scale * scale * 2);
+        for y in rect.top()..rect.bottom() {
+            let line_start = data.len();
+            for x in rect.left()..rect.right() {
+                let color = if frame.get_pixel(x as usize, y as usize) {
+                    &light
+                } else {
+                    &dark
+                };
+                for _ in 0..scale {
+                    data.extend_from_slice(color);
+                }
+            }
+            let line = data[line_start..].to_vec();
+            for _ in 1..scale {
+                data.extend_from_slice(&line[..]);
+            }
+        }
+
+        let panel_rect = gfx::Rect::xywh(
+            rect.left() * scale as i16,
+            rect.top() * scale as i16,
+            rect.size.width * scale,
+            rect.size.height * scale,
+        );
+        self.set_window(panel_rect)?;
+        self.command(COMMAND_MEMORY_WRITE, &data[..])
+    }
+
+    fn show_frame_internal(&mut self, frame: gfx::Frame) -> Result<()> {
+        assert!(frame.size() == self.size());
+
+        match self.cur_frame.take() {
+            None => {
+                let rect = gfx::Rect::ps(gfx::Vector::zero(), frame.size());
+                self.send_rect(&frame, rect)?;
+            }
+            Some(old) => {
+                let rect = get_changed_rect(&frame, &old);
+                if rect.size.width > 0 {
+                    self.send_rect(&frame, rect)?;
+                }
+                self.old_frame = Some(old);
+            }
+        };
+        self.cur_frame = Some(frame);
+
+        Ok(())
+    }
+}
+
+impl DisplayDriver for Tft {
+    fn size(&self) -> gfx::Size {
+        gfx::Size::wh(
+            self.config.panel_size.width / self.config.scale,
+            self.config.panel_size.height / self.config.scale,
+        )
+    }
+
+    fn show_frame(&mut self, frame: gfx::Frame) {
+        if let Err(e) = self.show_frame_internal(frame) {
+            println!("Tft: Failed to push a frame: {}", e.msg);
+        }
+    }
+
+    fn get_frame(&mut self) -> gfx::Frame {
+        match self.old_frame.take() {
+            Some(mut f) => {
+                f.clear();
+                f
+            }
+            None => gfx::Frame::new(self.size()),
+        }
+    }
+}
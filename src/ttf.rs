@@ -0,0 +1,405 @@
+// Reader for the TrueType tables that are not exposed by font_rs.
+
+use std::cmp;
+
+fn get_u16(data: &[u8], offset: usize) -> Option<u16> {
+    if offset + 2 > data.len() {
+        None
+    } else {
+        Some(((data[offset] as u16) << 8) | data[offset + 1] as u16)
+    }
+}
+
+fn get_i16(data: &[u8], offset: usize) -> Option<i16> {
+    get_u16(data, offset).map(|v| v as i16)
+}
+
+fn get_u32(data: &[u8], offset: usize) -> Option<u32> {
+    if offset + 4 > data.len() {
+        None
+    } else {
+        Some(((get_u16(data, offset)? as u32) << 16) | get_u16(data, offset + 2)? as u32)
+    }
+}
+
+// Returns glyph ID for |codepoint| from a format 12 cmap subtable.
+fn lookup_format12(subtable: &[u8], codepoint: u32) -> Option<u16> {
+    let num_groups = get_u32(subtable, 12)? as usize;
+    let (mut lo, mut hi) = (0, num_groups);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let group = 16 + mid * 12;
+        let start = get_u32(subtable, group)?;
+        let end = get_u32(subtable, group + 4)?;
+        if codepoint < start {
+            hi = mid;
+        } else if codepoint > end {
+            lo = mid + 1;
+        } else {
+            let id = get_u32(subtable, group + 8)? + (codepoint - start);
+            return if id == 0 || id > 0xffff {
+                None
+            } else {
+                Some(id as u16)
+            };
+        }
+    }
+    None
+}
+
+// Returns index of |glyph| in a Coverage table, or None when the glyph is
+// not covered.
+fn get_coverage_index(coverage: &[u8], glyph: u16) -> Option<usize> {
+    let count = get_u16(coverage, 2)? as usize;
+    let (mut lo, mut hi) = (0, count);
+    match get_u16(coverage, 0)? {
+        1 => {
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let g = get_u16(coverage, 4 + mid * 2)?;
+                if glyph < g {
+                    hi = mid;
+                } else if glyph > g {
+                    lo = mid + 1;
+                } else {
+                    return Some(mid);
+                }
+            }
+            None
+        }
+        2 => {
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let range = 4 + mid * 6;
+                let start = get_u16(coverage, range)?;
+                let end = get_u16(coverage, range + 2)?;
+                if glyph < start {
+                    hi = mid;
+                } else if glyph > end {
+                    lo = mid + 1;
+                } else {
+                    let start_index = get_u16(coverage, range + 4)? as usize;
+                    return Some(start_index + (glyph - start) as usize);
+                }
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+// Returns class of |glyph| from a ClassDef table. Glyphs that are not
+// listed are in class 0.
+fn get_glyph_class(class_def: &[u8], glyph: u16) -> u16 {
+    match get_u16(class_def, 0) {
+        Some(1) => {
+            let start = get_u16(class_def, 2).unwrap_or(0);
+            let count = get_u16(class_def, 4).unwrap_or(0);
+            if glyph < start || glyph - start >= count {
+                0
+            } else {
+                get_u16(class_def, 6 + (glyph - start) as usize * 2).unwrap_or(0)
+            }
+        }
+        Some(2) => {
+            let count = get_u16(class_def, 2).unwrap_or(0) as usize;
+            let (mut lo, mut hi) = (0, count);
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let range = 4 + mid * 6;
+                let start = get_u16(class_def, range).unwrap_or(0);
+                let end = get_u16(class_def, range + 2).unwrap_or(0);
+                if glyph < start {
+                    hi = mid;
+                } else if glyph > end {
+                    lo = mid + 1;
+                } else {
+                    return get_u16(class_def, range + 4).unwrap_or(0);
+                }
+            }
+            0
+        }
+        _ => 0,
+    }
+}
+
+// Size of a GPOS ValueRecord with the specified format.
+fn get_value_record_size(format: u16) -> usize {
+    format.count_ones() as usize * 2
+}
+
+// Returns XAdvance from a ValueRecord, if the record has it.
+fn get_x_advance(data: &[u8], record: usize, format: u16) -> Option<i16> {
+    if format & 0x0004 == 0 {
+        return None;
+    }
+    get_i16(data, record + get_value_record_size(format & 0x0003))
+}
+
+// Returns horizontal adjustment for the pair of glyphs from a GPOS PairPos
+// subtable.
+fn get_pair_adjustment(subtable: &[u8], left: u16, right: u16) -> Option<i16> {
+    let coverage = subtable.get((get_u16(subtable, 2)? as usize)..)?;
+    let index = get_coverage_index(coverage, left)?;
+    let format1 = get_u16(subtable, 4)?;
+    let format2 = get_u16(subtable, 6)?;
+    let size1 = get_value_record_size(format1);
+    let size2 = get_value_record_size(format2);
+    match get_u16(subtable, 0)? {
+        1 => {
+            let pair_set_offset = get_u16(subtable, 10 + index * 2)? as usize;
+            let pair_set = subtable.get(pair_set_offset..)?;
+            let record_size = 2 + size1 + size2;
+            let (mut lo, mut hi) = (0, get_u16(pair_set, 0)? as usize);
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let record = 2 + mid * record_size;
+                let second = get_u16(pair_set, record)?;
+                if right < second {
+                    hi = mid;
+                } else if right > second {
+                    lo = mid + 1;
+                } else {
+                    return get_x_advance(pair_set, record + 2, format1);
+                }
+            }
+            None
+        }
+        2 => {
+            let class_def1 = subtable.get((get_u16(subtable, 8)? as usize)..)?;
+            let class_def2 = subtable.get((get_u16(subtable, 10)? as usize)..)?;
+            let class2_count = get_u16(subtable, 14)? as usize;
+            let class1 = get_glyph_class(class_def1, left) as usize;
+            let class2 = get_glyph_class(class_def2, right) as usize;
+            let record = 16 + (class1 * class2_count + class2) * (size1 + size2);
+            get_x_advance(subtable, record, format1)
+        }
+        _ => None,
+    }
+}
+
+// Returns kerning value for the pair of glyphs from a format 0 kern
+// subtable.
+fn get_kern_pair(subtable: &[u8], left: u16, right: u16) -> Option<i16> {
+    let key = ((left as u32) << 16) | right as u32;
+    let (mut lo, mut hi) = (0, get_u16(subtable, 6)? as usize);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let pair = 14 + mid * 6;
+        let k = get_u32(subtable, pair)?;
+        if key < k {
+            hi = mid;
+        } else if key > k {
+            lo = mid + 1;
+        } else {
+            return get_i16(subtable, pair + 4);
+        }
+    }
+    None
+}
+
+// Positions of the subtables with pair kerning in the font data, so they
+// don't need to be searched for each pair of glyphs. GPOS PairPos subtables
+// are used when the font has them, otherwise kern table is used.
+pub struct KerningTables {
+    gpos: Vec<usize>,
+    kern: Vec<usize>,
+}
+
+pub struct Sfnt<'a> {
+    data: &'a [u8],
+}
+
+// Vertical metrics from hhea table, in font units.
+pub struct HorizontalHeader {
+    pub ascender: i16,
+    pub descender: i16,
+    pub line_gap: i16,
+}
+
+impl<'a> Sfnt<'a> {
+    pub fn new(data: &'a [u8]) -> Sfnt<'a> {
+        Sfnt { data }
+    }
+
+    // Returns offset and length of the table with the specified tag.
+    fn find_table(&self, tag: &[u8; 4]) -> Option<(usize, usize)> {
+        let num_tables = get_u16(self.data, 4)? as usize;
+        for i in 0..num_tables {
+            let record = 12 + i * 16;
+            if self.data.get(record..(record + 4))? == &tag[..] {
+                let offset = get_u32(self.data, record + 8)? as usize;
+                let length = get_u32(self.data, record + 12)? as usize;
+                return Some((offset, length));
+            }
+        }
+        None
+    }
+
+    pub fn get_table(&self, tag: &[u8; 4]) -> Option<&'a [u8]> {
+        let (offset, length) = self.find_table(tag)?;
+        // Offsets come from the font file, so the sum may overflow on 32-bit
+        // targets.
+        offset
+            .checked_add(length)
+            .and_then(|end| self.data.get(offset..end))
+    }
+
+    pub fn get_units_per_em(&self) -> Option<u16> {
+        get_u16(self.get_table(b"head")?, 18)
+    }
+
+    pub fn get_horizontal_header(&self) -> Option<HorizontalHeader> {
+        let hhea = self.get_table(b"hhea")?;
+        Some(HorizontalHeader {
+            ascender: get_i16(hhea, 4)?,
+            descender: get_i16(hhea, 6)?,
+            line_gap: get_i16(hhea, 8)?,
+        })
+    }
+
+    // Returns advance width of the glyph from hmtx table, in font units.
+    pub fn get_advance_width(&self, glyph: u16) -> Option<u16> {
+        let num_metrics = get_u16(self.get_table(b"hhea")?, 34)?;
+        if num_metrics == 0 {
+            return None;
+        }
+        // Glyphs after the last entry have the same advance.
+        let index = cmp::min(glyph, num_metrics - 1) as usize;
+        get_u16(self.get_table(b"hmtx")?, index * 4)
+    }
+
+    pub fn get_kerning_tables(&self) -> KerningTables {
+        let gpos = self.get_gpos_pair_tables().unwrap_or_default();
+        let kern = if gpos.is_empty() {
+            self.get_kern_tables().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        KerningTables { gpos, kern }
+    }
+
+    // Returns PairPos subtables of all lookups used by 'kern' feature.
+    fn get_gpos_pair_tables(&self) -> Option<Vec<usize>> {
+        let (gpos_offset, _) = self.find_table(b"GPOS")?;
+        let gpos = self.data.get(gpos_offset..)?;
+        let feature_list = gpos.get((get_u16(gpos, 6)? as usize)..)?;
+        let lookup_list_offset = gpos_offset + get_u16(gpos, 8)? as usize;
+        let lookup_list = self.data.get(lookup_list_offset..)?;
+
+        let mut lookups = Vec::new();
+        for i in 0..(get_u16(feature_list, 0)? as usize) {
+            let record = 2 + i * 6;
+            if feature_list.get(record..(record + 4))? != b"kern" {
+                continue;
+            }
+            let feature = feature_list.get((get_u16(feature_list, record + 4)? as usize)..)?;
+            for j in 0..(get_u16(feature, 2)? as usize) {
+                lookups.push(get_u16(feature, 4 + j * 2)?);
+            }
+        }
+        lookups.sort();
+        lookups.dedup();
+
+        let mut result = Vec::new();
+        for index in lookups {
+            let lookup_offset =
+                lookup_list_offset + get_u16(lookup_list, 2 + index as usize * 2)? as usize;
+            let lookup = self.data.get(lookup_offset..)?;
+            let lookup_type = get_u16(lookup, 0)?;
+            for i in 0..(get_u16(lookup, 4)? as usize) {
+                let subtable_offset = lookup_offset + get_u16(lookup, 6 + i * 2)? as usize;
+                match lookup_type {
+                    2 => result.push(subtable_offset),
+
+                    // Extension subtable that points to a PairPos subtable.
+                    9 => {
+                        let extension = self.data.get(subtable_offset..)?;
+                        if get_u16(extension, 2)? == 2 {
+                            result.push(subtable_offset + get_u32(extension, 4)? as usize);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Some(result)
+    }
+
+    // Returns horizontal format 0 subtables of kern table.
+    fn get_kern_tables(&self) -> Option<Vec<usize>> {
+        let (kern_offset, _) = self.find_table(b"kern")?;
+        let kern = self.data.get(kern_offset..)?;
+        let mut result = Vec::new();
+        let mut offset = 4;
+        for _ in 0..get_u16(kern, 2)? {
+            let length = get_u16(kern, offset + 2)? as usize;
+            let coverage = get_u16(kern, offset + 4)?;
+            // Format 0 with only the horizontal bit set.
+            if coverage & 0xff07 == 0x0001 {
+                result.push(kern_offset + offset);
+            }
+            offset += length;
+        }
+        Some(result)
+    }
+
+    // Returns horizontal kerning for the pair of glyphs, in font units.
+    pub fn get_kerning(&self, tables: &KerningTables, left: u16, right: u16) -> i16 {
+        for &offset in tables.gpos.iter() {
+            let value = self
+                .data
+                .get(offset..)
+                .and_then(|subtable| get_pair_adjustment(subtable, left, right));
+            if let Some(value) = value {
+                return value;
+            }
+        }
+        let mut result = 0;
+        for &offset in tables.kern.iter() {
+            if let Some(subtable) = self.data.get(offset..) {
+                result += get_kern_pair(subtable, left, right).unwrap_or(0);
+            }
+        }
+        result
+    }
+
+    // Looks up glyph ID in the full Unicode cmap subtable, which is needed
+    // for characters outside of the Basic Multilingual Plane. font_rs only
+    // reads the BMP subtable, so it should be used when this returns None.
+    pub fn lookup_glyph_id(&self, codepoint: u32) -> Option<u16> {
+        let cmap = self.get_table(b"cmap")?;
+        let num_tables = get_u16(cmap, 2)? as usize;
+        for i in 0..num_tables {
+            let record = 4 + i * 8;
+            let platform = get_u16(cmap, record)?;
+            let encoding = get_u16(cmap, record + 2)?;
+            if (platform, encoding) != (3, 10) && (platform, encoding) != (0, 4) {
+                continue;
+            }
+            let subtable = cmap.get((get_u32(cmap, record + 4)? as usize)..)?;
+            if get_u16(subtable, 0)? == 12 {
+                return lookup_format12(subtable, codepoint);
+            }
+        }
+        None
+    }
+
+    // Returns true if the glyph has no outline in glyf table.
+    pub fn is_empty_glyph(&self, glyph: u16) -> bool {
+        let (loca, long_offsets) = match (self.get_table(b"loca"), self.get_table(b"head")) {
+            (Some(loca), Some(head)) => (loca, get_i16(head, 50) == Some(1)),
+            _ => return false,
+        };
+        let index = glyph as usize;
+        let (start, end) = if long_offsets {
+            (get_u32(loca, index * 4), get_u32(loca, index * 4 + 4))
+        } else {
+            (
+                get_u16(loca, index * 2).map(|v| v as u32),
+                get_u16(loca, index * 2 + 2).map(|v| v as u32),
+            )
+        };
+        start.is_some() && start == end
+    }
+}